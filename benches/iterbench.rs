@@ -39,3 +39,14 @@ fn chunked_sum(bench: &mut Bencher) {
         img.buf().chunks(img.stride()).flat_map(|row| row[0..img.width()].iter()).copied().map(|p| p as usize).sum::<usize>()
     });
 }
+
+#[bench]
+#[cfg(feature = "alloc")]
+fn changed_tiles_4k(bench: &mut Bencher) {
+    let prev = ImgVec::new_filled(3840, 2160, 0u8);
+    let next = prev.clone();
+
+    bench.iter(|| {
+        imgref::compare::changed_tiles(prev.as_ref(), next.as_ref(), 8).len()
+    });
+}
@@ -0,0 +1,71 @@
+#ifndef IMGREF_H
+#define IMGREF_H
+
+/* Generated with cbindgen from src/capi.rs. Don't edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// Description of an image in memory, with a C-compatible layout, for passing images between Rust and C.
+//
+// Row `y` starts `y * stride_bytes` bytes after `data`, and has `width` pixels of `elem_size` bytes each.
+// The descriptor doesn't own the pixels, and doesn't keep track of their type or lifetime.
+typedef struct ImgDesc {
+  // Address of the first (top left) pixel
+  void *data;
+  // Width in pixels
+  uint32_t width;
+  // Height in pixels
+  uint32_t height;
+  // Distance between starts of rows, in bytes
+  size_t stride_bytes;
+  // Size of one pixel in bytes
+  size_t elem_size;
+} ImgDesc;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Allocate a `width`×`height` image with `elem_size`-byte pixels, all set to 0. Rows have no padding.
+//
+// The returned `data` is NULL if the memory can't be allocated. Free the image with `imgref_free()`.
+struct ImgDesc imgref_create(uint32_t width,
+                             uint32_t height,
+                             size_t elem_size);
+
+// Free an image returned by `imgref_create()` or `imgref_copy()`. Does nothing if its `data` is NULL.
+//
+// # Safety
+//
+// The descriptor must be unchanged, and must not be a sub-image. The image can't be used after it's freed.
+void imgref_free(struct ImgDesc img);
+
+// Describe the `width`×`height` area of `img` that starts at `left`, `top`, without copying any pixels.
+//
+// The sub-image points to the pixels of `img`, so it's valid only as long as `img` is, and must not be freed.
+// The returned `data` is NULL if the area is out of bounds or `img` is invalid.
+//
+// # Safety
+//
+// `img` must describe memory that can be read.
+struct ImgDesc imgref_sub_image(struct ImgDesc img,
+                                uint32_t left,
+                                uint32_t top,
+                                uint32_t width,
+                                uint32_t height);
+
+// Copy the pixels of `img` into a new image without padding. Free it with `imgref_free()`.
+//
+// The returned `data` is NULL if `img` is invalid or the memory can't be allocated.
+//
+// # Safety
+//
+// `img` must describe initialized memory that can be read.
+struct ImgDesc imgref_copy(struct ImgDesc img);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* IMGREF_H */
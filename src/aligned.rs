@@ -0,0 +1,191 @@
+use crate::{Img, ImgError, ImgExt, ImgVec};
+use alloc::alloc::{alloc, dealloc, handle_alloc_error, Layout};
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+use core::{fmt, mem, slice};
+
+/// Image in a buffer allocated at an address that is a multiple of a chosen number of bytes,
+/// with every row starting at such an address, e.g. for SIMD loads.
+///
+/// Make it with [`try_new_byte_aligned()`](ImgVecAligned::try_new_byte_aligned).
+pub type ImgVecAligned<Pixel> = Img<AlignedBuf<Pixel>>;
+
+/// Heap buffer with a chosen alignment. It's the container used by [`ImgVecAligned`].
+pub struct AlignedBuf<T> {
+    ptr: NonNull<T>,
+    len: usize,
+    /// Used for allocating and freeing the buffer. Nothing is allocated if its size is 0.
+    layout: Layout,
+}
+
+// SAFETY: it owns its elements, like a Vec
+unsafe impl<T: Send> Send for AlignedBuf<T> {}
+unsafe impl<T: Sync> Sync for AlignedBuf<T> {}
+
+impl<T> AlignedBuf<T> {
+    /// Allocate a buffer of `len` elements returned by `element(index)`. The `layout` must fit them.
+    fn try_from_fn(layout: Layout, len: usize, mut element: impl FnMut(usize) -> T) -> Result<Self, ImgError> {
+        debug_assert!(layout.size() >= len * mem::size_of::<T>() && layout.align() >= mem::align_of::<T>());
+        let ptr = if layout.size() == 0 {
+            // SAFETY: alignment is never 0, and such a pointer is valid for zero-sized accesses
+            unsafe { NonNull::new_unchecked(layout.align() as *mut T) }
+        } else {
+            // SAFETY: the size isn't zero
+            NonNull::new(unsafe { alloc(layout) }.cast()).ok_or(ImgError::AllocationFailed { pixels: len })?
+        };
+        // len is increased as elements are written, so that Drop cleans up if `element` panics
+        let mut buf = Self { ptr, len: 0, layout };
+        for i in 0..len {
+            // SAFETY: the allocation is large enough for `len` elements
+            unsafe { buf.ptr.as_ptr().add(i).write(element(i)) };
+            buf.len = i + 1;
+        }
+        Ok(buf)
+    }
+
+    /// Alignment of the buffer in bytes
+    #[inline]
+    #[must_use]
+    pub fn alignment(&self) -> usize {
+        self.layout.align()
+    }
+}
+
+impl<T> Drop for AlignedBuf<T> {
+    fn drop(&mut self) {
+        // SAFETY: the first `len` elements are initialized, and the buffer was allocated with `layout`
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len));
+            if self.layout.size() != 0 {
+                dealloc(self.ptr.as_ptr().cast(), self.layout);
+            }
+        }
+    }
+}
+
+impl<T: Clone> Clone for AlignedBuf<T> {
+    /// Copies the elements into a new buffer with the same alignment
+    fn clone(&self) -> Self {
+        Self::try_from_fn(self.layout, self.len, |i| self[i].clone())
+            .unwrap_or_else(|_| handle_alloc_error(self.layout))
+    }
+}
+
+impl<T> Deref for AlignedBuf<T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        // SAFETY: the first `len` elements are initialized
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for AlignedBuf<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` elements are initialized, and they're borrowed uniquely
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> AsRef<[T]> for AlignedBuf<T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> AsMut<[T]> for AlignedBuf<T> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for AlignedBuf<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: Clone> ImgVecAligned<T> {
+    /// Make an image with every pixel set to `fill`, and the first pixel of every row at an address that is a multiple of `align_bytes`.
+    ///
+    /// Unlike [`ImgVec::new_aligned()`], which aligns the stride to a number of pixels, this aligns the memory addresses of rows, e.g. for SIMD loads.
+    /// The buffer is allocated with that alignment, and the stride is rounded up to keep every row aligned,
+    /// so [`row_alignment()`](ImgExt::row_alignment) is at least `align_bytes`. Sub-images keep the alignment only if they start at aligned columns.
+    ///
+    /// Returns an error if `align_bytes` isn't a power of two, the image is too large, or the memory can't be allocated.
+    pub fn try_new_byte_aligned(width: usize, height: usize, align_bytes: usize, fill: T) -> Result<Self, ImgError> {
+        if !align_bytes.is_power_of_two() {
+            return Err(ImgError::Unaligned { align: align_bytes });
+        }
+        crate::check_dimensions(width, height)?;
+        let step = aligned_step(mem::size_of::<T>(), align_bytes);
+        let too_large = ImgError::TooLarge { width, height };
+        let stride = width.max(1).checked_add(step - 1).ok_or(too_large.clone())? / step * step;
+        let len = stride.checked_mul(height).ok_or(too_large.clone())?;
+        let layout = Layout::array::<T>(len).and_then(|layout| layout.align_to(align_bytes)).map_err(|_| too_large)?;
+        let buf = AlignedBuf::try_from_fn(layout, len, |_| fill.clone())?;
+        Ok(Img::new_stride(buf, width, height, stride))
+    }
+
+    /// Copy the visible pixels into a new image, without padding. It's aligned only as much as a `Vec` is.
+    #[must_use]
+    pub fn to_img_vec(&self) -> ImgVec<T> {
+        let (buf, width, height) = self.as_ref().to_contiguous_buf();
+        Img::new_stride(buf.into_owned(), width, height, width.max(1))
+    }
+}
+
+/// Number of pixels between aligned addresses. Rows are aligned if the stride is a multiple of it.
+fn aligned_step(size: usize, align_bytes: usize) -> usize {
+    align_bytes >> size.trailing_zeros().min(align_bytes.trailing_zeros())
+}
+
+#[test]
+fn aligned() {
+    use alloc::vec;
+
+    for align in [1, 16, 32, 64, 4096] {
+        let img = ImgVecAligned::try_new_byte_aligned(5, 3, align, [0u8; 3]).unwrap();
+        assert!(img.row_alignment() >= align);
+        assert_eq!(0, img.stride() * 3 % align);
+        assert_eq!(15, img.pixels().count());
+        assert_eq!(img.as_ref(), ImgVec::new(vec![[0u8; 3]; 15], 5, 3));
+        assert!(img.rows().all(|row| row.as_ptr() as usize % align == 0));
+
+        // pixel types with size larger than their alignment
+        let img = ImgVecAligned::try_new_byte_aligned(7, 3, align, [1f32; 4]).unwrap();
+        assert!(img.row_alignment() >= align);
+        assert!(img.clone().row_alignment() >= align);
+        let img = ImgVecAligned::try_new_byte_aligned(3, 2, align, [1u16; 4]).unwrap();
+        assert!(img.row_alignment() >= align);
+        assert_eq!(img.to_img_vec(), ImgVec::new(vec![[1u16; 4]; 6], 3, 2));
+    }
+    let img = ImgVecAligned::try_new_byte_aligned(3, 2, 16, 1u32).unwrap();
+    assert_eq!(4, img.stride());
+    assert_eq!(16, img.buf().alignment());
+    assert!(img.sub_image(0, 1, 2, 1).row_alignment() >= 16);
+    assert!(img.sub_image(1, 0, 2, 2).row_alignment() < 16);
+
+    assert_eq!(Some(ImgError::Unaligned { align: 24 }), ImgVecAligned::try_new_byte_aligned(1, 1, 24, 0u8).err());
+    assert_eq!(Some(ImgError::TooLarge { width: usize::MAX, height: 1 }), ImgVecAligned::try_new_byte_aligned(usize::MAX, 1, 64, 0u8).err());
+    assert_eq!(Some(ImgError::TooLarge { width: u32::MAX as usize, height: 0 }), ImgVecAligned::try_new_byte_aligned(u32::MAX as usize, 0, 64, 0u8).err());
+    assert_eq!(Some(ImgError::TooLarge { width: 1 << 31, height: 1 << 31 }), ImgVecAligned::try_new_byte_aligned(1 << 31, 1 << 31, 64, 0u64).err());
+    assert_eq!(2, ImgVecAligned::try_new_byte_aligned(2, 2, 64, ()).unwrap().rows().count());
+    assert!(ImgVecAligned::try_new_byte_aligned(0, 0, 64, 0u8).unwrap().buf().is_empty());
+}
+
+#[test]
+fn aligned_drop() {
+    use alloc::rc::Rc;
+
+    let fill = Rc::new(());
+    let img = ImgVecAligned::try_new_byte_aligned(3, 2, 64, Rc::clone(&fill)).unwrap();
+    let copy = img.clone();
+    assert_eq!(1 + 2 * img.buf().len(), Rc::strong_count(&fill));
+    drop((img, copy));
+    assert_eq!(1, Rc::strong_count(&fill));
+}
@@ -0,0 +1,158 @@
+use crate::{Img, ImgRef, ImgRefMut, ImgVec};
+use alloc::sync::Arc;
+use core::fmt;
+use core::ops::Deref;
+
+/// Image in a shared, reference-counted buffer. Cloning it and taking sub-images is cheap and doesn't copy pixels.
+///
+/// Sub-images are owned handles (with no lifetime), so they can be sent to other threads.
+///
+/// Create it from an [`ImgVec`] with `.into()`.
+pub type ImgArc<Pixel> = Img<ArcSlice<Pixel>>;
+
+/// Part of an `Arc<[T]>`. It's the container used by [`ImgArc`].
+pub struct ArcSlice<T> {
+    data: Arc<[T]>,
+    start: usize,
+    end: usize,
+}
+
+impl<T> ArcSlice<T> {
+    /// Share the whole slice
+    #[inline]
+    #[must_use]
+    pub fn new(data: Arc<[T]>) -> Self {
+        let end = data.len();
+        Self { data, start: 0, end }
+    }
+
+    /// The entire shared buffer, and the range of it that this slice covers
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> (Arc<[T]>, core::ops::Range<usize>) {
+        (self.data, self.start..self.end)
+    }
+}
+
+impl<T> Clone for ArcSlice<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { data: Arc::clone(&self.data), start: self.start, end: self.end }
+    }
+}
+
+impl<T> Deref for ArcSlice<T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        &self.data[self.start..self.end]
+    }
+}
+
+impl<T> AsRef<[T]> for ArcSlice<T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArcSlice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> From<ImgVec<T>> for ImgArc<T> {
+    #[inline]
+    fn from(img: ImgVec<T>) -> Self {
+        img.map_buf(|buf| ArcSlice::new(buf.into()))
+    }
+}
+
+impl<T: Clone> From<ImgArc<T>> for ImgVec<T> {
+    /// Copies the visible pixels
+    #[inline]
+    fn from(img: ImgArc<T>) -> Self {
+        img.to_img_vec()
+    }
+}
+
+impl<T> ImgArc<T> {
+    /// Make a reference to this image to pass it to functions that take `ImgRef`
+    #[inline]
+    #[must_use]
+    pub fn as_ref(&self) -> ImgRef<'_, T> {
+        self.new_buf(&**self.buf())
+    }
+
+    /// Make another handle to a part of the image, without copying any pixels.
+    ///
+    /// The new image keeps the whole buffer alive.
+    ///
+    /// # Panics
+    ///
+    /// If the coordinates are out of bounds
+    #[must_use]
+    #[track_caller]
+    pub fn sub_image(&self, left: usize, top: usize, width: usize, height: usize) -> Self {
+        assert!(top + height <= self.height());
+        assert!(left + width <= self.width());
+        let buf = self.buf();
+        let (start, end, stride) = crate::sub_image(left, top, width, height, self.stride(), buf.len());
+        let buf = ArcSlice { data: Arc::clone(&buf.data), start: buf.start + start, end: buf.start + end };
+        Img::new_stride(buf, width, height, stride)
+    }
+}
+
+impl<T: Clone> ImgArc<T> {
+    /// Mutable access to the pixels, like [`Arc::make_mut()`].
+    ///
+    /// If the buffer is shared with other handles, only the pixels of this image (without padding) are copied into a new buffer.
+    pub fn make_mut(&mut self) -> ImgRefMut<'_, T> {
+        if Arc::get_mut(&mut self.buf_mut().data).is_none() {
+            *self = self.to_img_vec().into();
+        }
+        let (width, height, stride) = (self.width(), self.height(), self.stride());
+        let buf = self.buf_mut();
+        let data = Arc::get_mut(&mut buf.data).expect("unique buffer");
+        Img::new_stride(&mut data[buf.start..buf.end], width, height, stride)
+    }
+
+    /// Copy the visible pixels into a new image, without padding
+    #[must_use]
+    pub fn to_img_vec(&self) -> ImgVec<T> {
+        let (buf, width, height) = self.as_ref().to_contiguous_buf();
+        Img::new_stride(buf.into_owned(), width, height, width.max(1))
+    }
+}
+
+#[test]
+fn shared() {
+    use crate::ImgExt;
+    use alloc::vec;
+
+    let img: ImgArc<u8> = Img::new_stride(vec![1, 2, 3, 4, 5, 6, 7, 8, 9], 2, 3, 3).into();
+    let sub = img.sub_image(1, 1, 1, 2);
+    assert_eq!(sub.as_ref(), Img::new(vec![5, 8], 1, 2));
+    let sub_sub = sub.sub_image(0, 1, 1, 1);
+    assert_eq!(8, sub_sub[(0usize, 0usize)]);
+    assert_eq!(&[5], &sub[0]);
+
+    let thread_sub = sub.clone();
+    let sum = std::thread::spawn(move || thread_sub.pixels().map(u32::from).sum::<u32>()).join().unwrap();
+    assert_eq!(13, sum);
+
+    let mut copy = sub.clone();
+    copy.make_mut()[(0usize, 1usize)] = 80;
+    assert_eq!(copy.as_ref(), Img::new(vec![5, 80], 1, 2));
+    assert_eq!(sub.as_ref(), Img::new(vec![5, 8], 1, 2));
+    assert_eq!(2, copy.buf().len());
+    assert_eq!(8, img[(1usize, 2usize)]);
+
+    let ptr = copy.buf().as_ptr();
+    copy.make_mut()[(0usize, 0usize)] = 50;
+    assert_eq!(ptr, copy.buf().as_ptr());
+    assert_eq!(ImgVec::from(copy), Img::new(vec![50, 80], 1, 2));
+    assert_eq!(img.to_img_vec(), Img::new(vec![1, 2, 4, 5, 7, 8], 2, 3));
+}
@@ -0,0 +1,303 @@
+use crate::{Img, ImgExt, ImgExtMut, ImgRef, ImgRefMut, PixelsIter, PixelsIterMut, RowsIter, RowsIterMut};
+#[cfg(feature = "alloc")]
+use crate::ImgVec;
+use core::hash::{Hash, Hasher};
+use core::{ops, slice};
+
+/// Image with dimensions known at compile time, stored inline (e.g. on the stack) without allocating.
+///
+/// Good for small kernels, icons and blocks. Use [`as_ref()`](Self::as_ref) to pass it to functions that take `ImgRef`.
+///
+/// ```rust
+/// use imgref::ImgArray;
+/// const KERNEL: ImgArray<i8, 3, 3> = ImgArray::new([[0, -1, 0], [-1, 4, -1], [0, -1, 0]]);
+/// assert_eq!(4, KERNEL[(1usize, 1usize)]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ImgArray<T, const W: usize, const H: usize> {
+    rows: [[T; W]; H],
+}
+
+impl<T, const W: usize, const H: usize> ImgArray<T, W, H> {
+    /// Width of all images of this type
+    pub const WIDTH: usize = W;
+    /// Height of all images of this type
+    pub const HEIGHT: usize = H;
+
+    /// Make an image from rows of pixels
+    #[inline]
+    #[must_use]
+    pub const fn new(rows: [[T; W]; H]) -> Self {
+        Self { rows }
+    }
+
+    /// Width of the image in pixels
+    #[inline(always)]
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    /// Height of the image in pixels
+    #[inline(always)]
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    /// Rows of pixels
+    #[inline(always)]
+    #[must_use]
+    pub const fn as_array(&self) -> &[[T; W]; H] {
+        &self.rows
+    }
+
+    /// Rows of pixels
+    #[inline(always)]
+    #[must_use]
+    pub fn into_array(self) -> [[T; W]; H] {
+        self.rows
+    }
+
+    /// All pixels in one slice, `W*H` long. There's no padding.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: arrays are contiguous, so `[[T; W]; H]` has the same layout as `[T; W*H]`
+        unsafe { slice::from_raw_parts(self.rows.as_ptr().cast::<T>(), W * H) }
+    }
+
+    /// All pixels in one mutable slice, `W*H` long. There's no padding.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: arrays are contiguous, so `[[T; W]; H]` has the same layout as `[T; W*H]`
+        unsafe { slice::from_raw_parts_mut(self.rows.as_mut_ptr().cast::<T>(), W * H) }
+    }
+
+    /// Make a reference to this image to pass it to functions that take `ImgRef`
+    #[inline]
+    #[must_use]
+    pub fn as_ref(&self) -> ImgRef<'_, T> {
+        Img::new_stride(self.as_slice(), W, H, W.max(1))
+    }
+
+    /// Make a mutable reference to this image to pass it to functions that take `ImgRefMut`
+    #[inline]
+    #[must_use]
+    pub fn as_mut(&mut self) -> ImgRefMut<'_, T> {
+        Img::new_stride(self.as_mut_slice(), W, H, W.max(1))
+    }
+
+    /// Iterate over rows of the image as slices
+    #[inline]
+    pub fn rows(&self) -> RowsIter<'_, T> {
+        ImgExt::rows(self)
+    }
+
+    /// Iterate over rows of the image as mutable slices
+    #[inline]
+    pub fn rows_mut(&mut self) -> RowsIterMut<'_, T> {
+        ImgExtMut::rows_mut(self)
+    }
+
+    /// Iterate over pixels of the image mutably
+    #[inline]
+    pub fn pixels_mut(&mut self) -> PixelsIterMut<'_, T> {
+        PixelsIterMut::new(self.as_mut())
+    }
+}
+
+impl<T: Copy, const W: usize, const H: usize> ImgArray<T, W, H> {
+    /// Make an image with every pixel set to `value`
+    #[inline]
+    #[must_use]
+    pub const fn filled(value: T) -> Self {
+        Self { rows: [[value; W]; H] }
+    }
+
+    /// Iterate over pixels of the image
+    #[inline]
+    pub fn pixels(&self) -> PixelsIter<'_, T> {
+        PixelsIter::new(self.as_ref())
+    }
+}
+
+impl<T, const W: usize, const H: usize> ImgExt<T> for ImgArray<T, W, H> {
+    #[inline(always)]
+    #[cfg(feature = "deprecated")]
+    fn width_padded(&self) -> usize {
+        W
+    }
+
+    #[inline(always)]
+    #[cfg(feature = "deprecated")]
+    fn height_padded(&self) -> usize {
+        H
+    }
+
+    #[inline(always)]
+    #[cfg(feature = "deprecated")]
+    fn rows_padded(&self) -> slice::Chunks<'_, T> {
+        self.as_slice().chunks(W.max(1))
+    }
+
+    #[inline(always)]
+    fn as_ref(&self) -> ImgRef<'_, T> {
+        Self::as_ref(self)
+    }
+}
+
+impl<T, const W: usize, const H: usize> ImgExtMut<T> for ImgArray<T, W, H> {
+    #[inline(always)]
+    #[cfg(feature = "deprecated")]
+    fn rows_padded_mut(&mut self) -> slice::ChunksMut<'_, T> {
+        self.as_mut_slice().chunks_mut(W.max(1))
+    }
+
+    #[inline(always)]
+    fn as_mut(&mut self) -> ImgRefMut<'_, T> {
+        Self::as_mut(self)
+    }
+}
+
+impl<T, const W: usize, const H: usize> From<[[T; W]; H]> for ImgArray<T, W, H> {
+    #[inline(always)]
+    fn from(rows: [[T; W]; H]) -> Self {
+        Self { rows }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone, const W: usize, const H: usize> From<ImgArray<T, W, H>> for ImgVec<T> {
+    #[inline]
+    fn from(img: ImgArray<T, W, H>) -> Self {
+        Img::new_stride(img.as_slice().to_vec(), W, H, W.max(1))
+    }
+}
+
+impl<T, const W: usize, const H: usize> ops::Index<usize> for ImgArray<T, W, H> {
+    type Output = [T; W];
+
+    /// Take n-th row
+    #[inline(always)]
+    #[track_caller]
+    fn index(&self, row: usize) -> &[T; W] {
+        &self.rows[row]
+    }
+}
+
+impl<T, const W: usize, const H: usize> ops::IndexMut<usize> for ImgArray<T, W, H> {
+    /// Take n-th row
+    #[inline(always)]
+    #[track_caller]
+    fn index_mut(&mut self, row: usize) -> &mut [T; W] {
+        &mut self.rows[row]
+    }
+}
+
+macro_rules! impl_array_index {
+    ($index:ty) => {
+        impl<T, const W: usize, const H: usize> ops::Index<($index, $index)> for ImgArray<T, W, H> {
+            type Output = T;
+
+            /// Read a pixel at `(x,y)` location
+            #[inline(always)]
+            #[track_caller]
+            fn index(&self, (x, y): ($index, $index)) -> &T {
+                &self.rows[y as usize][x as usize]
+            }
+        }
+
+        impl<T, const W: usize, const H: usize> ops::IndexMut<($index, $index)> for ImgArray<T, W, H> {
+            /// Write a pixel at `(x,y)` location
+            #[inline(always)]
+            #[track_caller]
+            fn index_mut(&mut self, (x, y): ($index, $index)) -> &mut T {
+                &mut self.rows[y as usize][x as usize]
+            }
+        }
+    };
+}
+
+impl_array_index! {usize}
+impl_array_index! {u32}
+
+impl<T: Hash, const W: usize, const H: usize> Hash for ImgArray<T, W, H> {
+    /// Same hash as an `ImgRef` with the same pixels
+    #[inline]
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        self.as_ref().hash(state);
+    }
+}
+
+impl<T, U, const W: usize, const H: usize> PartialEq<ImgArray<U, W, H>> for ImgArray<T, W, H> where T: PartialEq<U> {
+    #[inline]
+    fn eq(&self, other: &ImgArray<U, W, H>) -> bool {
+        self.rows.iter().zip(&other.rows).all(|(a, b)| a[..] == b[..])
+    }
+}
+
+impl<T: Eq, const W: usize, const H: usize> Eq for ImgArray<T, W, H> {}
+
+impl<'a, T, U, const W: usize, const H: usize> PartialEq<ImgRef<'a, U>> for ImgArray<T, W, H> where T: PartialEq<U> {
+    #[inline]
+    fn eq(&self, other: &ImgRef<'a, U>) -> bool {
+        self.as_ref().eq(other)
+    }
+}
+
+impl<T, U, const W: usize, const H: usize> PartialEq<ImgArray<U, W, H>> for ImgRef<'_, T> where T: PartialEq<U> {
+    #[inline]
+    fn eq(&self, other: &ImgArray<U, W, H>) -> bool {
+        self.eq(&other.as_ref())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U, const W: usize, const H: usize> PartialEq<ImgVec<U>> for ImgArray<T, W, H> where T: PartialEq<U> {
+    #[inline]
+    fn eq(&self, other: &ImgVec<U>) -> bool {
+        self.as_ref().eq(&other.as_ref())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U, const W: usize, const H: usize> PartialEq<ImgArray<U, W, H>> for ImgVec<T> where T: PartialEq<U> {
+    #[inline]
+    fn eq(&self, other: &ImgArray<U, W, H>) -> bool {
+        self.as_ref().eq(&other.as_ref())
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn array() {
+    use alloc::vec;
+    use std::collections::hash_map::DefaultHasher;
+
+    const IMG: ImgArray<u8, 3, 2> = ImgArray::new([[1, 2, 3], [4, 5, 6]]);
+    const WIDTH: usize = IMG.width();
+    assert_eq!((3, 2), (WIDTH, ImgArray::<u8, 3, 2>::HEIGHT));
+
+    let mut img = IMG;
+    assert_eq!(6, img[(2usize, 1usize)]);
+    img[(0u32, 1u32)] = 40;
+    assert_eq!(&[40, 5, 6], &img[1]);
+    img.rows_mut().for_each(|row| row[1] *= 10);
+    img.pixels_mut().for_each(|px| *px += 1);
+    assert_eq!(vec![2, 21, 4, 41, 51, 7], img.pixels().collect::<vec::Vec<_>>());
+    assert_eq!(img.as_ref().sub_image(1, 0, 2, 2), Img::new(vec![21, 4, 51, 7], 2, 2));
+
+    let vec = Img::new_stride(vec![2, 21, 4, 0, 41, 51, 7], 3, 2, 4);
+    assert_eq!(img, vec);
+    assert_eq!(vec, img);
+    assert_eq!(img, ImgVec::from(img));
+    assert_ne!(img, IMG);
+    let hash = |h: &dyn Fn(&mut DefaultHasher)| { let mut s = DefaultHasher::new(); h(&mut s); s.finish() };
+    assert_eq!(hash(&|s| img.hash(s)), hash(&|s| vec.hash(s)));
+
+    let empty = ImgArray::<u16, 0, 4>::filled(1);
+    assert_eq!(0, empty.rows().count());
+    assert_eq!(ImgArray::filled(7), ImgArray::<u8, 2, 2>::new([[7; 2]; 2]));
+}
@@ -0,0 +1,186 @@
+use crate::canvas::replicate_edges;
+use crate::compose::copy_into;
+use crate::{Img, ImgRef, ImgVec, Rect};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Identifies an image added to the [`AtlasBuilder`]. Use it to find the image in the [`Atlas`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SpriteId(usize);
+
+impl SpriteId {
+    /// Position of the image in order of [`AtlasBuilder::add()`] calls
+    #[inline]
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Collects images to pack into an [`Atlas`]. Create it with [`Atlas::builder()`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct AtlasBuilder<'slice, T> {
+    sprites: Vec<ImgRef<'slice, T>>,
+    max_width: usize,
+    padding: usize,
+    bleed: bool,
+}
+
+impl<'slice, T: Copy> AtlasBuilder<'slice, T> {
+    /// Empty space to add around every image, in pixels. Default is 0.
+    #[inline]
+    pub fn padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// If `true`, the padding is filled with copies of the images' edge pixels rather than the background,
+    /// which prevents the background from bleeding into the images when they're sampled with filtering.
+    #[inline]
+    pub fn bleed(mut self, bleed: bool) -> Self {
+        self.bleed = bleed;
+        self
+    }
+
+    /// Add an image to be packed
+    #[inline]
+    pub fn add(&mut self, img: ImgRef<'slice, T>) -> SpriteId {
+        self.sprites.push(img);
+        SpriteId(self.sprites.len() - 1)
+    }
+
+    /// Pack the images into rows ("shelves") of the atlas, tallest first.
+    ///
+    /// The atlas is at most `max_width` pixels wide, unless an image (with padding) is wider than that.
+    /// Area not covered by images is filled with `background`.
+    #[must_use]
+    pub fn build(self, background: T) -> Atlas<T> {
+        let padding = self.padding;
+        let mut order: Vec<usize> = (0..self.sprites.len()).collect();
+        order.sort_by_key(|&i| core::cmp::Reverse(self.sprites[i].height()));
+
+        let mut rects = vec![Rect::default(); self.sprites.len()];
+        let (mut x, mut y, mut shelf_height, mut width) = (0, 0, 0, 0);
+        for i in order {
+            let sprite = self.sprites[i];
+            let slot_width = sprite.width() + 2 * padding;
+            if x > 0 && x + slot_width > self.max_width {
+                x = 0;
+                y += shelf_height;
+                shelf_height = 0;
+            }
+            rects[i] = Rect::new(x + padding, y + padding, sprite.width(), sprite.height());
+            x += slot_width;
+            width = width.max(x);
+            shelf_height = shelf_height.max(sprite.height() + 2 * padding);
+        }
+        let height = y + shelf_height;
+
+        let stride = width.max(1);
+        let mut img = Img::new_stride(vec![background; stride * height], width, height, stride);
+        for (sprite, rect) in self.sprites.iter().zip(&rects) {
+            copy_into(img.sub_image_mut(rect.left, rect.top, rect.width, rect.height), *sprite);
+            if self.bleed && padding > 0 && !rect.is_empty() {
+                let slot = img.sub_image_mut(rect.left - padding, rect.top - padding, rect.width + 2 * padding, rect.height + 2 * padding);
+                replicate_edges(slot, Rect::new(padding, padding, rect.width, rect.height));
+            }
+        }
+        Atlas { img, rects }
+    }
+}
+
+/// Many images packed into one. Create it with [`Atlas::builder()`].
+#[derive(Debug, Clone)]
+pub struct Atlas<T> {
+    img: ImgVec<T>,
+    rects: Vec<Rect>,
+}
+
+impl<T: Copy> Atlas<T> {
+    /// Start collecting images for an atlas that will be at most `max_width` pixels wide
+    #[inline]
+    pub fn builder<'slice>(max_width: usize) -> AtlasBuilder<'slice, T> {
+        AtlasBuilder {
+            sprites: Vec::new(),
+            max_width,
+            padding: 0,
+            bleed: false,
+        }
+    }
+}
+
+impl<T> Atlas<T> {
+    /// Position of the image in the atlas
+    ///
+    /// # Panics
+    ///
+    /// If the id is from a different atlas
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn rect(&self, id: SpriteId) -> Rect {
+        self.rects[id.0]
+    }
+
+    /// Positions of all images, in order they've been added
+    #[inline]
+    #[must_use]
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// Reference to the packed image in the atlas, without copying
+    ///
+    /// # Panics
+    ///
+    /// If the id is from a different atlas
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn sub_image(&self, id: SpriteId) -> ImgRef<'_, T> {
+        let rect = self.rect(id);
+        self.img.sub_image(rect.left, rect.top, rect.width, rect.height)
+    }
+
+    /// The whole atlas image
+    #[inline]
+    #[must_use]
+    pub fn img(&self) -> ImgRef<'_, T> {
+        self.img.as_ref()
+    }
+
+    /// Take the atlas image and positions of the images in it
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> (ImgVec<T>, Vec<Rect>) {
+        (self.img, self.rects)
+    }
+}
+
+#[test]
+fn atlas() {
+    let a = Img::new(vec![1u8; 6], 3, 2);
+    let b = Img::new(vec![2u8; 4], 2, 2);
+    let c = Img::new_stride(vec![3u8, 4, 0, 5, 6, 0, 7, 8], 2, 3, 3);
+
+    let mut builder = Atlas::builder(6);
+    let ids = [builder.add(a.as_ref()), builder.add(b.as_ref()), builder.add(c.as_ref())];
+    let atlas = builder.build(0);
+    assert_eq!(Rect::new(2, 0, 3, 2), atlas.rect(ids[0]));
+    assert_eq!(Rect::new(0, 3, 2, 2), atlas.rect(ids[1]));
+    assert_eq!(Rect::new(0, 0, 2, 3), atlas.rect(ids[2]));
+    assert_eq!((5, 5), (atlas.img().width(), atlas.img().height()));
+    assert_eq!(a, atlas.sub_image(ids[0]));
+    assert_eq!(b, atlas.sub_image(ids[1]));
+    assert_eq!(c, atlas.sub_image(ids[2]));
+
+    let mut builder = Atlas::builder(100).padding(1).bleed(true);
+    let id = builder.add(c.as_ref());
+    builder.add(b.as_ref());
+    let atlas = builder.build(0);
+    assert_eq!(Rect::new(1, 1, 2, 3), atlas.rect(id));
+    assert_eq!(c, atlas.sub_image(id));
+    assert_eq!(&[3, 3, 4, 4, 2, 2, 2, 2], &atlas.img()[0]);
+    assert_eq!(&[7, 7, 8, 8, 0, 0, 0, 0], &atlas.img()[4]);
+}
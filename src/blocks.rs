@@ -0,0 +1,173 @@
+use crate::{ImgRef, Rect};
+use core::iter::FusedIterator;
+
+#[cfg(all(test, feature = "alloc"))]
+use alloc::vec;
+
+impl<'slice, T: Copy> ImgRef<'slice, T> {
+    /// Iterate over the image in blocks of `W`×`H` pixels (e.g. 4×4 or 8×8 blocks for codecs),
+    /// left to right, top to bottom.
+    ///
+    /// Blocks at the right and bottom edges may be only partially covered by the image.
+    /// See [`Block::to_array()`] for getting them with the edge pixels replicated.
+    ///
+    /// # Panics
+    ///
+    /// If `W` or `H` is 0
+    #[inline]
+    #[track_caller]
+    pub fn blocks<const W: usize, const H: usize>(&self) -> BlocksIter<'slice, T, W, H> {
+        assert!(W > 0 && H > 0);
+        BlocksIter {
+            img: *self,
+            x: 0,
+            y: 0,
+            columns: (self.width() + W - 1) / W,
+            rows: if self.width() > 0 { (self.height() + H - 1) / H } else { 0 },
+        }
+    }
+}
+
+/// A block of the image. Created by [`ImgRef::blocks()`].
+#[derive(Debug, Copy, Clone)]
+pub struct Block<'slice, T, const W: usize, const H: usize> {
+    /// Column of this block (in blocks, not pixels)
+    pub x: usize,
+    /// Row of this block (in blocks, not pixels)
+    pub y: usize,
+    /// The part of the block that is inside the image. It's smaller than `W`×`H` at the right and bottom edges.
+    pub img: ImgRef<'slice, T>,
+}
+
+impl<'slice, T: Copy, const W: usize, const H: usize> Block<'slice, T, W, H> {
+    /// Position and size of the block's pixels in the image. The size is smaller than `W`×`H` at the right and bottom edges.
+    #[inline]
+    #[must_use]
+    pub fn rect(&self) -> Rect {
+        Rect::new(self.x * W, self.y * H, self.img.width(), self.img.height())
+    }
+
+    /// Whether all `W`×`H` pixels of the block are inside the image
+    #[inline]
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.img.width() == W && self.img.height() == H
+    }
+
+    /// The block as a reference to the image, if all of its pixels are inside the image
+    #[inline]
+    #[must_use]
+    pub fn as_complete(&self) -> Option<ImgRef<'slice, T>> {
+        if self.is_complete() { Some(self.img) } else { None }
+    }
+
+    /// Copy of the block's pixels as `H` rows of `W` pixels.
+    ///
+    /// Pixels outside of the image are copies of the nearest pixel at the right or bottom edge.
+    #[must_use]
+    pub fn to_array(&self) -> [[T; W]; H] {
+        let mut out = [[self.img[(0usize, 0usize)]; W]; H];
+        let mut row = &self.img[0];
+        for (y, out_row) in out.iter_mut().enumerate() {
+            if y < self.img.height() {
+                row = &self.img[y];
+            }
+            let edge = row[row.len() - 1];
+            out_row[..row.len()].copy_from_slice(row);
+            out_row[row.len()..].fill(edge);
+        }
+        out
+    }
+}
+
+/// Iterator over blocks of an image. Created by [`ImgRef::blocks()`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct BlocksIter<'slice, T, const W: usize, const H: usize> {
+    img: ImgRef<'slice, T>,
+    x: usize,
+    y: usize,
+    columns: usize,
+    rows: usize,
+}
+
+impl<'slice, T, const W: usize, const H: usize> BlocksIter<'slice, T, W, H> {
+    /// Number of blocks horizontally and vertically
+    #[inline]
+    #[must_use]
+    pub fn grid_size(&self) -> (usize, usize) {
+        (self.columns, self.rows)
+    }
+}
+
+impl<'slice, T: Copy, const W: usize, const H: usize> Iterator for BlocksIter<'slice, T, W, H> {
+    type Item = Block<'slice, T, W, H>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= self.rows {
+            return None;
+        }
+        let (x, y) = (self.x, self.y);
+        self.x += 1;
+        if self.x >= self.columns {
+            self.x = 0;
+            self.y += 1;
+        }
+        let left = x * W;
+        let top = y * H;
+        let width = W.min(self.img.width() - left);
+        let height = H.min(self.img.height() - top);
+        Some(Block { x, y, img: self.img.sub_image(left, top, width, height) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: Copy, const W: usize, const H: usize> ExactSizeIterator for BlocksIter<'_, T, W, H> {
+    #[inline]
+    fn len(&self) -> usize {
+        (self.rows.saturating_sub(self.y) * self.columns).saturating_sub(self.x)
+    }
+}
+
+impl<T: Copy, const W: usize, const H: usize> FusedIterator for BlocksIter<'_, T, W, H> {}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn blocks() {
+    let img = ImgRef::new_stride(&[1, 2, 3, 0,
+                                   4, 5, 6, 0,
+                                   7, 8, 9][..], 3, 3, 4);
+    let mut iter = img.blocks::<2, 2>();
+    assert_eq!((2, 2), iter.grid_size());
+    assert_eq!(4, iter.len());
+
+    let b = iter.next().unwrap();
+    assert!(b.is_complete());
+    assert_eq!(Rect::new(0, 0, 2, 2), b.rect());
+    assert_eq!([[1, 2], [4, 5]], b.to_array());
+    assert_eq!(b.as_complete().unwrap(), ImgRef::new(&[1, 2, 4, 5][..], 2, 2));
+
+    let b = iter.next().unwrap();
+    assert_eq!((1, 0), (b.x, b.y));
+    assert!(b.as_complete().is_none());
+    assert_eq!(Rect::new(2, 0, 1, 2), b.rect());
+    assert_eq!([[3, 3], [6, 6]], b.to_array());
+
+    let b = iter.next().unwrap();
+    assert_eq!([[7, 8], [7, 8]], b.to_array());
+    let b = iter.next().unwrap();
+    assert_eq!([[9, 9], [9, 9]], b.to_array());
+    assert_eq!(0, iter.len());
+    assert!(iter.next().is_none());
+
+    let img = ImgRef::new(&[1u8; 8 * 16][..], 8, 16);
+    let blocks = vec![[[1u8; 4]; 4]; 8];
+    assert_eq!(blocks, img.blocks::<4, 4>().map(|b| b.to_array()).collect::<vec::Vec<_>>());
+    assert_eq!(0, ImgRef::new_stride(&[0u8; 0][..], 0, 5, 1).blocks::<4, 4>().count());
+}
@@ -0,0 +1,212 @@
+use crate::{Img, ImgError, ImgVec};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+
+/// Creates an [`ImgVec`] one row at a time, e.g. from a decoder's output.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ImgVecBuilder<T> {
+    buf: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> ImgVecBuilder<T> {
+    /// Start an image that is `width` pixels wide
+    #[inline]
+    pub fn new(width: usize) -> Self {
+        Self::with_capacity(width, 0)
+    }
+
+    /// Start an image that is `width` pixels wide, with space reserved for `height` rows
+    #[inline]
+    pub fn with_capacity(width: usize, height: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(width * height),
+            width,
+            height: 0,
+        }
+    }
+
+    /// Add a row at the bottom of the image. It must be exactly `width` pixels long.
+    #[inline]
+    pub fn push_row(&mut self, row: &[T]) -> Result<(), ImgError> {
+        if row.len() != self.width {
+            return Err(ImgError::RowLengthMismatch { row: self.height, expected: self.width, found: row.len() });
+        }
+        self.buf.extend_from_slice(row);
+        self.height += 1;
+        Ok(())
+    }
+
+    /// Number of rows added so far
+    #[inline]
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Make an image out of the rows added so far. It has no padding.
+    #[inline]
+    #[must_use]
+    pub fn finish(self) -> ImgVec<T> {
+        Img::new_stride(self.buf, self.width, self.height, self.width.max(1))
+    }
+}
+
+impl<T> ImgVec<T> {
+    /// Create an image with pixels returned by `callback(x, y)`, called in order top to bottom, left to right. It has no padding.
+    #[must_use]
+    pub fn from_fn<F>(width: usize, height: usize, mut callback: F) -> Self where F: FnMut(usize, usize) -> T {
+        let mut buf = Vec::with_capacity(width * height);
+        for y in 0..height {
+            buf.extend((0..width).map(|x| callback(x, y)));
+        }
+        Img::new_stride(buf, width, height, width.max(1))
+    }
+
+    /// Same as [`from_fn()`](Self::from_fn), but returns an error instead of aborting when the image is too large to allocate.
+    pub fn try_from_fn<F>(width: usize, height: usize, mut callback: F) -> Result<Self, ImgError> where F: FnMut(usize, usize) -> T {
+        let mut buf = try_with_capacity(width, height)?;
+        for y in 0..height {
+            buf.extend((0..width).map(|x| callback(x, y)));
+        }
+        Ok(Img::new_stride(buf, width, height, width.max(1)))
+    }
+}
+
+impl<T: Clone> ImgVec<T> {
+    /// Create an image with every pixel set to `value`. It has no padding.
+    #[inline]
+    #[must_use]
+    pub fn new_filled(width: usize, height: usize, value: T) -> Self {
+        Img::new_stride(vec![value; width * height], width, height, width.max(1))
+    }
+
+    /// Same as [`new_filled()`](Self::new_filled), but returns an error instead of aborting when the image is too large to allocate.
+    ///
+    /// Use it when the dimensions come from untrusted input.
+    pub fn try_new_filled(width: usize, height: usize, value: T) -> Result<Self, ImgError> {
+        let mut buf = try_with_capacity(width, height)?;
+        buf.resize(width * height, value);
+        Ok(Img::new_stride(buf, width, height, width.max(1)))
+    }
+
+    /// Collect rows, each exactly `width` pixels long, into an image.
+    ///
+    /// Returns an error if any row has a different length.
+    pub fn from_rows<I, R>(rows: I, width: usize) -> Result<Self, ImgError> where I: IntoIterator<Item = R>, R: AsRef<[T]> {
+        let rows = rows.into_iter();
+        let mut builder = ImgVecBuilder::with_capacity(width, rows.size_hint().0);
+        for row in rows {
+            builder.push_row(row.as_ref())?;
+        }
+        Ok(builder.finish())
+    }
+
+    /// Add rows at the bottom of the image, increasing its height. Each row must be exactly `width` pixels long.
+    ///
+    /// If the image has a stride larger than its width, the padding of the new rows is filled with copies of their last pixel.
+    ///
+    /// If any row has a wrong length, an error is returned and the image keeps its previous height.
+    #[allow(deprecated)]
+    pub fn append_rows<I, R>(&mut self, rows: I) -> Result<(), ImgError> where I: IntoIterator<Item = R>, R: AsRef<[T]> {
+        let width = self.width();
+        let stride = self.stride();
+        let old_height = self.height();
+        let mut height = old_height;
+        if width > 0 {
+            // make the padding of the last row explicit, so that new rows start at multiples of the stride
+            let full_len = stride * old_height;
+            if self.buf.len() < full_len {
+                let last = self.buf[self.buf.len() - 1].clone();
+                self.buf.resize(full_len, last);
+            }
+            self.buf.truncate(full_len);
+        }
+        for row in rows {
+            let row = row.as_ref();
+            if row.len() != width {
+                self.buf.truncate(stride * old_height);
+                return Err(ImgError::RowLengthMismatch { row: height, expected: width, found: row.len() });
+            }
+            if let Some(last) = row.last() {
+                self.buf.extend_from_slice(row);
+                self.buf.resize(self.buf.len() + stride - width, last.clone());
+            }
+            height += 1;
+        }
+        *self = Img::new_stride(mem::take(&mut self.buf), width, height, stride);
+        Ok(())
+    }
+}
+
+/// Empty `Vec` with space for `width * height` pixels, or an error if that's too much
+pub(crate) fn try_with_capacity<T>(width: usize, height: usize) -> Result<Vec<T>, ImgError> {
+    crate::check_dimensions(width, height)?;
+    let pixels = width.checked_mul(height).ok_or(ImgError::TooLarge { width, height })?;
+    let mut buf = Vec::new();
+    buf.try_reserve_exact(pixels).map_err(|_| ImgError::AllocationFailed { pixels })?;
+    Ok(buf)
+}
+
+#[test]
+fn builder() {
+    let mut b = ImgVecBuilder::new(2);
+    b.push_row(&[1, 2]).unwrap();
+    assert_eq!(Err(ImgError::RowLengthMismatch { row: 1, expected: 2, found: 3 }), b.push_row(&[1, 2, 3]));
+    b.push_row(&[3, 4]).unwrap();
+    assert_eq!(2, b.height());
+    let img = b.finish();
+    assert_eq!(img, Img::new(vec![1, 2, 3, 4], 2, 2));
+
+    assert_eq!(img, ImgVec::from_rows([[1, 2], [3, 4]], 2).unwrap());
+    assert_eq!(img, ImgVec::from_rows(img.rows(), 2).unwrap());
+    assert!(ImgVec::from_rows(img.rows(), 3).is_err());
+}
+
+#[test]
+fn from_fn() {
+    let img = ImgVec::from_fn(3, 2, |x, y| x + 10 * y);
+    assert_eq!(img, Img::new(vec![0, 1, 2, 10, 11, 12], 3, 2));
+    assert_eq!(img.map_buf(|buf| buf.into_iter().map(|_| 7).collect::<Vec<_>>()), ImgVec::new_filled(3, 2, 7));
+}
+
+#[test]
+fn fallible() {
+    assert_eq!(Ok(ImgVec::new_filled(3, 2, 1u8)), ImgVec::try_new_filled(3, 2, 1u8));
+    assert_eq!(ImgVec::from_fn(4, 3, |x, y| x * y), ImgVec::try_from_fn(4, 3, |x, y| x * y).unwrap());
+    // pixels don't need to be Clone
+    struct NotClone(usize);
+    let img = ImgVec::from_fn(2, 1, |x, _| NotClone(x));
+    assert_eq!(1, img.buf()[1].0);
+    assert_eq!(1, ImgVec::try_from_fn(1, 1, |_, _| NotClone(0)).unwrap().buf().len());
+    assert_eq!(Err(ImgError::TooLarge { width: usize::MAX, height: 2 }), ImgVec::try_new_filled(usize::MAX, 2, 0u8));
+    assert_eq!(Err(ImgError::TooLarge { width: u32::MAX as usize, height: 0 }), ImgVec::try_new_filled(u32::MAX as usize, 0, 0u8));
+    assert_eq!(Err(ImgError::TooLarge { width: 0, height: u32::MAX as usize }), ImgVec::try_from_fn(0, u32::MAX as usize, |_, _| 0u8));
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(Err(ImgError::AllocationFailed { pixels: 1 << 60 }), ImgVec::try_from_fn(1 << 30, 1 << 30, |_, _| 0u16));
+
+    let img = Img::new_stride(&[1, 2, 0, 3, 4][..], 2, 2, 3);
+    assert_eq!(Ok(Img::new(vec![1, 2, 3, 4], 2, 2)), img.try_to_owned());
+    assert_eq!(&[1, 2, 3, 4], &img.try_to_contiguous_buf().unwrap().0[..]);
+    let cow: Img<alloc::borrow::Cow<'_, [u8]>> = img.into();
+    assert_eq!(Ok(Img::new(vec![1, 2, 3, 4], 2, 2)), cow.try_into_owned());
+}
+
+#[test]
+fn append_rows() {
+    let mut img = Img::new_stride(vec![1, 2, 0, 3, 4], 2, 2, 3);
+    img.append_rows([[5, 6]]).unwrap();
+    assert_eq!(img, Img::new(vec![1, 2, 3, 4, 5, 6], 2, 3));
+    assert_eq!(&[1, 2, 0, 3, 4, 4, 5, 6, 6], &img.buf()[..]);
+
+    assert!(img.append_rows([&[7, 8][..], &[9][..]]).is_err());
+    assert_eq!(3, img.height());
+    assert_eq!(9, img.buf().len());
+
+    let mut img = ImgVec::<u8>::new_stride(vec![], 0, 0, 1);
+    img.append_rows([[]; 4]).unwrap();
+    assert_eq!(4, img.height());
+}
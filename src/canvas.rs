@@ -0,0 +1,292 @@
+use crate::{Img, ImgRefMut, ImgVec, Rect};
+use alloc::vec;
+use core::mem;
+
+impl<T: Copy> ImgVec<T> {
+    /// Create an image filled with `fill`, with the stride rounded up to a multiple of `align_px` pixels.
+    ///
+    /// The buffer has padding after every row, including the last one (it's `stride * height` pixels long).
+    ///
+    /// # Panics
+    ///
+    /// If `align_px` is 0, or the size of the buffer overflows `usize`
+    #[must_use]
+    #[track_caller]
+    pub fn new_aligned(width: usize, height: usize, align_px: usize, fill: T) -> Self {
+        let stride = round_up(width, align_px);
+        Img::new_stride(vec![fill; buf_len(stride, height)], width, height, stride)
+    }
+}
+
+impl<T: Copy + Default> ImgVec<T> {
+    /// Create an image with the buffer large enough for whole `pad_to_w`×`pad_to_h` blocks
+    /// (e.g. 16×16 macroblocks), filled with `T::default()`.
+    ///
+    /// The stride is rounded up to a multiple of `pad_to_w`, and the buffer has enough rows for
+    /// height rounded up to a multiple of `pad_to_h`. Use [`padded_view()`](Self::padded_view) to access the padding.
+    ///
+    /// # Panics
+    ///
+    /// If `pad_to_w` or `pad_to_h` is 0, or the size of the buffer overflows `usize`
+    #[must_use]
+    #[track_caller]
+    pub fn new_padded(width: usize, height: usize, pad_to_w: usize, pad_to_h: usize) -> Self {
+        let stride = round_up(width, pad_to_w);
+        let padded_height = round_up(height, pad_to_h);
+        Img::new_stride(vec![T::default(); buf_len(stride, padded_height)], width, height, stride)
+    }
+}
+
+impl<T> ImgVec<T> {
+    /// A mutable view of the entire buffer, including the padding: `stride()` pixels wide,
+    /// and as many rows as fit in the buffer.
+    ///
+    /// This is useful with images created with [`new_padded()`](Self::new_padded), for codecs that process whole blocks.
+    #[inline]
+    #[must_use]
+    pub fn padded_view(&mut self) -> ImgRefMut<'_, T> {
+        let stride = self.stride();
+        let buf = self.buf_mut().as_mut_slice();
+        let padded_height = buf.len() / stride;
+        Img::new_stride(buf, stride, padded_height, stride)
+    }
+}
+
+#[track_caller]
+fn round_up(size: usize, multiple: usize) -> usize {
+    assert!(multiple > 0);
+    let rounded = size.checked_add(multiple - 1).expect("image is too large: padded size overflows usize") / multiple * multiple;
+    rounded.max(multiple)
+}
+
+#[track_caller]
+fn padded_size(before: usize, size: usize, after: usize) -> usize {
+    before.checked_add(size).and_then(|s| s.checked_add(after)).expect("image is too large: padded size overflows usize")
+}
+
+#[track_caller]
+pub(crate) fn buf_len(stride: usize, height: usize) -> usize {
+    stride.checked_mul(height).expect("image is too large: buffer size overflows usize")
+}
+
+impl<T: Copy> ImgVec<T> {
+    /// Enlarge the canvas by adding the given number of pixels on each side, and set them to `fill`.
+    ///
+    /// The buffer is reused if it has enough capacity. The resulting image has no padding.
+    ///
+    /// # Panics
+    ///
+    /// If the size of the enlarged image overflows `usize`
+    #[track_caller]
+    pub fn pad(&mut self, left: usize, top: usize, right: usize, bottom: usize, fill: T) {
+        let width = self.width();
+        let height = self.height();
+        let new_width = padded_size(left, width, right);
+        let new_height = padded_size(top, height, bottom);
+        let new_stride = new_width.max(1);
+        self.reflow(new_stride, left, top, buf_len(new_stride, new_height), fill);
+
+        #[allow(deprecated)]
+        let buf = &mut self.buf;
+        buf[..top * new_stride].fill(fill);
+        buf[(top + height) * new_stride..].fill(fill);
+        for row in buf.chunks_mut(new_stride).skip(top).take(height) {
+            row[..left].fill(fill);
+            row[left + width..].fill(fill);
+        }
+        *self = Img::new_stride(mem::take(buf), new_width, new_height, new_stride);
+    }
+
+    /// Enlarge the canvas by adding the given number of pixels on each side,
+    /// and set them to copies of the nearest pixel at the edge of the image.
+    ///
+    /// # Panics
+    ///
+    /// If the image is empty (has no edge pixels to copy), or the size of the enlarged image overflows `usize`
+    #[track_caller]
+    pub fn extend_edges(&mut self, left: usize, top: usize, right: usize, bottom: usize) {
+        let width = self.width();
+        let height = self.height();
+        assert!(width > 0 && height > 0, "can't extend edges of an empty image");
+        let fill = self[(0usize, 0usize)];
+        self.pad(left, top, right, bottom, fill);
+        replicate_edges(self.as_mut(), Rect::new(left, top, width, height));
+    }
+
+    /// Cut the image down to the given area, moving the rows to the start of the existing buffer.
+    ///
+    /// The resulting image has no padding.
+    ///
+    /// # Panics
+    ///
+    /// If the area is outside of the image, including when its right or bottom edge overflows `usize`
+    #[allow(deprecated)]
+    #[track_caller]
+    pub fn crop_in_place(&mut self, rect: Rect) {
+        assert!(rect.left.checked_add(rect.width).map_or(false, |right| right <= self.width()), "crop area is outside of the image");
+        assert!(rect.top.checked_add(rect.height).map_or(false, |bottom| bottom <= self.height()), "crop area is outside of the image");
+        let stride = self.stride();
+        let buf = &mut self.buf;
+        for y in 0..rect.height {
+            let start = (rect.top + y) * stride + rect.left;
+            buf.copy_within(start..start + rect.width, y * rect.width);
+        }
+        buf.truncate(rect.width * rect.height);
+        *self = Img::new_stride(mem::take(buf), rect.width, rect.height, rect.width.max(1));
+    }
+
+    /// Moves the rows to start at `(top + y) * new_stride + left`, and resizes the buffer to `new_len`.
+    ///
+    /// Pixels outside of the moved rows are left with unspecified values.
+    #[allow(deprecated)]
+    fn reflow(&mut self, new_stride: usize, left: usize, top: usize, new_len: usize, fill: T) {
+        let (_, width, height) = self.as_contiguous_buf();
+        debug_assert!(height == 0 || new_len >= (top + height - 1) * new_stride + left + width);
+        let buf = &mut self.buf;
+        if buf.len() < new_len {
+            buf.resize(new_len, fill);
+        }
+        for y in (0..height).rev() {
+            buf.copy_within(y * width..(y + 1) * width, (top + y) * new_stride + left);
+        }
+        buf.truncate(new_len);
+    }
+}
+
+impl<T: Copy + Default> ImgVec<T> {
+    /// Move the rows in the buffer to be `new_stride` pixels apart, growing or shrinking the padding.
+    ///
+    /// The buffer will be `new_stride * height` pixels long, and the padding will be set to `T::default()`.
+    ///
+    /// # Panics
+    ///
+    /// If the new stride is 0 or smaller than the width.
+    #[track_caller]
+    pub fn set_stride(&mut self, new_stride: usize) {
+        let width = self.width();
+        let height = self.height();
+        assert!(new_stride > 0 && new_stride >= width);
+        self.reflow(new_stride, 0, 0, new_stride * height, T::default());
+        #[allow(deprecated)]
+        let buf = &mut self.buf;
+        for row in buf.chunks_mut(new_stride) {
+            row[width..].fill(T::default());
+        }
+        *self = Img::new_stride(mem::take(buf), width, height, new_stride);
+    }
+}
+
+/// Set all pixels outside of the `inner` area to copies of the nearest pixel inside it
+#[track_caller]
+pub(crate) fn replicate_edges<T: Copy>(mut img: ImgRefMut<'_, T>, inner: Rect) {
+    assert!(!inner.is_empty());
+    for y in inner.top..inner.bottom() {
+        let row = &mut img[y];
+        let left = row[inner.left];
+        row[..inner.left].fill(left);
+        let right = row[inner.right() - 1];
+        row[inner.right()..].fill(right);
+    }
+    let width = img.width();
+    let height = img.height();
+    let stride = img.stride();
+    let buf = img.valid_buf_mut();
+    let top_row = inner.top * stride;
+    for y in 0..inner.top {
+        buf.copy_within(top_row..top_row + width, y * stride);
+    }
+    let bottom_row = (inner.bottom() - 1) * stride;
+    for y in inner.bottom()..height {
+        buf.copy_within(bottom_row..bottom_row + width, y * stride);
+    }
+}
+
+#[test]
+fn pad_and_extend() {
+    let mut img = Img::new_stride(vec![1, 2, 0,
+                                       3, 4], 2, 2, 3);
+    img.pad(1, 0, 2, 1, 9);
+    assert_eq!(img, Img::new(vec![9, 1, 2, 9, 9,
+                                  9, 3, 4, 9, 9,
+                                  9, 9, 9, 9, 9], 5, 3));
+    assert_eq!(5, img.stride());
+
+    let mut img = Img::new_stride(vec![1, 2, 0,
+                                       3, 4], 2, 2, 3);
+    img.extend_edges(2, 1, 1, 2);
+    assert_eq!(img, Img::new(vec![1, 1, 1, 2, 2,
+                                  1, 1, 1, 2, 2,
+                                  3, 3, 3, 4, 4,
+                                  3, 3, 3, 4, 4,
+                                  3, 3, 3, 4, 4], 5, 5));
+
+    let mut img = Img::new_stride(vec![0u8; 0], 0, 0, 1);
+    img.pad(1, 1, 1, 1, 7);
+    assert_eq!(img, Img::new(vec![7; 4], 2, 2));
+}
+
+#[test]
+fn aligned() {
+    let img = ImgVec::new_aligned(5, 3, 4, 1u8);
+    assert_eq!(8, img.stride());
+    assert_eq!(24, img.buf().len());
+    assert_eq!(15, img.pixels().map(usize::from).sum::<usize>());
+
+    let mut img = ImgVec::<u16>::new_padded(17, 9, 16, 8);
+    assert_eq!((17, 9, 32), (img.width(), img.height(), img.stride()));
+    let mut padded = img.padded_view();
+    assert_eq!((32, 16), (padded.width(), padded.height()));
+    padded[(31usize, 15usize)] = 1;
+    assert_eq!(1, *img.buf().last().unwrap());
+
+    assert_eq!(4, ImgVec::new_aligned(0, 1, 4, 0u8).stride());
+}
+
+#[test]
+#[should_panic(expected = "image is too large")]
+fn aligned_overflow() {
+    let _ = ImgVec::new_aligned(usize::MAX / 2, 4, 16, 0u8);
+}
+
+#[test]
+#[should_panic(expected = "image is too large")]
+fn padded_overflow() {
+    let _ = ImgVec::<u8>::new_padded(usize::MAX - 2, 1, 16, 1);
+}
+
+#[test]
+#[should_panic(expected = "image is too large")]
+fn pad_overflow() {
+    let mut img = ImgVec::new_filled(2, 2, 0u8);
+    img.pad(usize::MAX - 2, 0, 1, 0, 0);
+}
+
+#[test]
+#[should_panic(expected = "image is too large")]
+fn pad_buf_overflow() {
+    let mut img = ImgVec::new_filled(2, 2, 0u8);
+    img.extend_edges(0, usize::MAX / 4, usize::MAX / 4, 0);
+}
+
+#[test]
+#[should_panic(expected = "outside of the image")]
+fn crop_overflow() {
+    let mut img = ImgVec::new_filled(2, 2, 0u8);
+    img.crop_in_place(Rect::new(1, 0, usize::MAX, 1));
+}
+
+#[test]
+fn crop_and_stride() {
+    let mut img = ImgVec::new_stride((0..20).collect(), 4, 4, 5);
+    let capacity = img.buf().capacity();
+    img.crop_in_place(Rect::new(1, 2, 3, 2));
+    assert_eq!(img, Img::new(vec![11, 12, 13,
+                                  16, 17, 18], 3, 2));
+    assert_eq!(capacity, img.buf().capacity());
+
+    img.set_stride(5);
+    assert_eq!(&[11, 12, 13, 0, 0, 16, 17, 18, 0, 0], &img.buf()[..]);
+    img.set_stride(4);
+    assert_eq!(&[11, 12, 13, 0, 16, 17, 18, 0], &img.buf()[..]);
+    assert_eq!(img, Img::new(vec![11, 12, 13, 16, 17, 18], 3, 2));
+}
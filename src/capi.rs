@@ -0,0 +1,164 @@
+//! C functions for images described by [`ImgDesc`]. They're declared in `include/imgref.h`.
+//!
+//! The functions work on any pixel type, because they only copy bytes.
+//! Functions that fail return a descriptor with NULL `data`.
+//! They don't panic, because panics can't unwind into C.
+
+use crate::{ImgDesc, ImgExtMut, ImgRef, ImgRefMut};
+use alloc::alloc::{alloc_zeroed, dealloc, Layout};
+use core::ptr;
+
+/// Alignment of buffers allocated by [`imgref_create()`], enough for any primitive pixel type
+const ALIGN: usize = 16;
+
+const fn null_desc(width: u32, height: u32, elem_size: usize) -> ImgDesc {
+    ImgDesc { data: ptr::null_mut(), width, height, stride_bytes: 0, elem_size }
+}
+
+/// Layout of buffers allocated by [`imgref_create()`]
+fn layout(desc: &ImgDesc) -> Option<Layout> {
+    let size = desc.stride_bytes.checked_mul(desc.height as usize)?;
+    Layout::from_size_align(size, ALIGN).ok()
+}
+
+/// View of the image as bytes, or `None` if the descriptor is invalid
+unsafe fn as_bytes<'a>(desc: &ImgDesc) -> Option<ImgRef<'a, u8>> {
+    let width = (desc.width as usize).checked_mul(desc.elem_size)?;
+    ImgRef::from_raw_parts(desc.data.cast::<u8>(), width, desc.height as usize, desc.stride_bytes).ok()
+}
+
+/// Mutable view of the image as bytes, or `None` if the descriptor is invalid
+unsafe fn as_bytes_mut<'a>(desc: &ImgDesc) -> Option<ImgRefMut<'a, u8>> {
+    let width = (desc.width as usize).checked_mul(desc.elem_size)?;
+    ImgRefMut::from_raw_parts_mut(desc.data.cast::<u8>(), width, desc.height as usize, desc.stride_bytes).ok()
+}
+
+/// Allocate a `width`×`height` image with `elem_size`-byte pixels, all set to 0. Rows have no padding.
+///
+/// The returned `data` is NULL if the memory can't be allocated. Free the image with `imgref_free()`.
+#[no_mangle]
+pub extern "C" fn imgref_create(width: u32, height: u32, elem_size: usize) -> ImgDesc {
+    let stride_bytes = match (width as usize).checked_mul(elem_size) {
+        Some(s) => s,
+        None => return null_desc(width, height, elem_size),
+    };
+    let mut desc = ImgDesc { stride_bytes, ..null_desc(width, height, elem_size) };
+    desc.data = match layout(&desc) {
+        Some(layout) if layout.size() == 0 => ALIGN as *mut _,
+        // SAFETY: the size isn't zero
+        Some(layout) => unsafe { alloc_zeroed(layout) }.cast(),
+        None => return null_desc(width, height, elem_size),
+    };
+    desc
+}
+
+/// Free an image returned by `imgref_create()` or `imgref_copy()`. Does nothing if its `data` is NULL.
+///
+/// # Safety
+///
+/// The descriptor must be unchanged, and must not be a sub-image. The image can't be used after it's freed.
+#[no_mangle]
+pub unsafe extern "C" fn imgref_free(img: ImgDesc) {
+    if img.data.is_null() {
+        return;
+    }
+    if let Some(layout) = layout(&img) {
+        if layout.size() != 0 {
+            dealloc(img.data.cast(), layout);
+        }
+    }
+}
+
+/// Describe the `width`×`height` area of `img` that starts at `left`, `top`, without copying any pixels.
+///
+/// The sub-image points to the pixels of `img`, so it's valid only as long as `img` is, and must not be freed.
+/// The returned `data` is NULL if the area is out of bounds or `img` is invalid.
+///
+/// # Safety
+///
+/// `img` must describe memory that can be read.
+#[no_mangle]
+pub unsafe extern "C" fn imgref_sub_image(img: ImgDesc, left: u32, top: u32, width: u32, height: u32) -> ImgDesc {
+    let null = null_desc(width, height, img.elem_size);
+    if as_bytes(&img).is_none() ||
+        left.checked_add(width).map_or(true, |right| right > img.width) ||
+        top.checked_add(height).map_or(true, |bottom| bottom > img.height) {
+        return null;
+    }
+    let offset = (top as usize).checked_mul(img.stride_bytes)
+        .and_then(|y| y.checked_add((left as usize).checked_mul(img.elem_size)?));
+    let offset = match offset {
+        Some(offset) => offset,
+        None => return null,
+    };
+    // an empty area at the bottom edge may start past the end of the buffer,
+    // so the pointer isn't offset with `add()`, and it's never read from
+    ImgDesc {
+        data: img.data.cast::<u8>().wrapping_add(offset).cast(),
+        stride_bytes: img.stride_bytes,
+        ..null
+    }
+}
+
+/// Copy the pixels of `img` into a new image without padding. Free it with `imgref_free()`.
+///
+/// The returned `data` is NULL if `img` is invalid or the memory can't be allocated.
+///
+/// # Safety
+///
+/// `img` must describe initialized memory that can be read.
+#[no_mangle]
+pub unsafe extern "C" fn imgref_copy(img: ImgDesc) -> ImgDesc {
+    let src = match as_bytes(&img) {
+        Some(src) => src,
+        None => return null_desc(img.width, img.height, img.elem_size),
+    };
+    let copy = imgref_create(img.width, img.height, img.elem_size);
+    if let Some(mut dst) = as_bytes_mut(&copy) {
+        // both rows are `width * elem_size` bytes long
+        for (dst, src) in ImgExtMut::rows_mut(&mut dst).zip(src.rows()) {
+            dst.copy_from_slice(src);
+        }
+    }
+    copy
+}
+
+#[test]
+fn capi() {
+    use crate::Img;
+
+    let pixels = [1u16, 2, 3, 0, 4, 5, 6, 0];
+    let img = ImgDesc::try_from(Img::new_stride(&pixels[..], 3, 2, 4)).unwrap();
+    unsafe {
+        let sub = imgref_sub_image(img, 1, 0, 2, 2);
+        assert_eq!(Img::new_stride(&[2, 3, 0, 4, 5, 6][..], 2, 2, 4), sub.as_img_ref::<u16>().unwrap());
+        assert!(imgref_sub_image(img, 2, 1, 2, 1).data.is_null());
+        assert!(imgref_sub_image(img, 0, 3, 1, 0).data.is_null());
+        assert!(imgref_sub_image(img, u32::MAX, 0, 2, 1).data.is_null());
+
+        // empty areas at the edges are valid, even if they start past the end of the buffer
+        let bottom = imgref_sub_image(img, 0, 2, 1, 0);
+        assert_eq!(pixels.as_ptr().wrapping_add(8).cast(), bottom.data);
+        assert_eq!(0, bottom.as_img_ref::<u16>().unwrap().height());
+        let copy = imgref_copy(bottom);
+        assert_eq!((1, 0), (copy.width, copy.height));
+        imgref_free(copy);
+        let right = imgref_sub_image(img, 3, 0, 0, 2);
+        assert_eq!(0, right.as_img_ref::<u16>().unwrap().width());
+        imgref_free(imgref_copy(right));
+
+        let copy = imgref_copy(sub);
+        assert_eq!((4, 0), (copy.stride_bytes, copy.data as usize % ALIGN));
+        assert_eq!(Img::new(&[2, 3, 5, 6][..], 2, 2), copy.as_img_ref::<u16>().unwrap());
+        imgref_free(copy);
+
+        let new = imgref_create(3, 5, 4);
+        assert!(new.as_img_ref::<u32>().unwrap().pixels().all(|px| px == 0));
+        imgref_free(new);
+        let empty = imgref_create(0, 5, 4);
+        assert_eq!(0, imgref_copy(empty).as_img_ref::<u32>().unwrap().width());
+        imgref_free(empty);
+        assert!(imgref_create(u32::MAX, u32::MAX, usize::MAX).data.is_null());
+        imgref_free(null_desc(1, 1, 1));
+    }
+}
@@ -0,0 +1,361 @@
+//! Measuring differences between images, e.g. for checking quality of lossy compression in tests.
+//!
+//! All functions compare only pixels within `width`×`height` of each image, and ignore the padding (like `==` does).
+
+use crate::math;
+use crate::{ImgRef, Rect};
+#[cfg(feature = "alloc")]
+use crate::{Img, ImgVec};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(all(test, feature = "alloc"))]
+use alloc::vec;
+
+#[cfg(feature = "alloc")]
+mod ssim;
+#[cfg(feature = "alloc")]
+pub use ssim::*;
+
+/// Pixel types that can be compared channel by channel.
+///
+/// It's implemented for integer (signed and unsigned) and float scalars, arrays of them (e.g. `[u8; 4]`), and [`rgb`](https://lib.rs/crates/rgb) pixels when the `rgb` feature is enabled.
+pub trait PixelChannels: Copy {
+    /// Number of channels (color components) in a pixel
+    const CHANNELS: usize;
+
+    /// Largest value of a channel, e.g. 255 for `u8`. Floats are assumed to be in the 0..=1 range.
+    ///
+    /// For signed integers it's the size of their range, e.g. 255 for `i8`.
+    const MAX_VALUE: f64;
+
+    /// Pixel type of [`abs_diff()`](Self::abs_diff). It's `Self`, except for signed integers, which use unsigned ones (e.g. `u8` for `i8`),
+    /// because their differences can be larger than their `MAX`.
+    type Diff: Copy;
+
+    /// Value of the channel at `index`. Must be less than `CHANNELS`.
+    fn channel(&self, index: usize) -> f64;
+
+    /// Absolute difference between the pixels, for each channel separately
+    #[must_use]
+    fn abs_diff(self, other: Self) -> Self::Diff;
+}
+
+macro_rules! impl_scalar_channels {
+    ($($t:ty => $max:expr),*) => {$(
+        impl PixelChannels for $t {
+            const CHANNELS: usize = 1;
+            const MAX_VALUE: f64 = $max;
+            type Diff = Self;
+
+            #[inline(always)]
+            fn channel(&self, _: usize) -> f64 {
+                f64::from(*self)
+            }
+
+            #[inline(always)]
+            fn abs_diff(self, other: Self) -> Self {
+                if self > other { self - other } else { other - self }
+            }
+        }
+    )*};
+}
+
+impl_scalar_channels! {u8 => 255., u16 => 65535., u32 => 4294967295., f32 => 1., f64 => 1.}
+
+macro_rules! impl_signed_channels {
+    ($($t:ty => $diff:ty, $max:expr),*) => {$(
+        impl PixelChannels for $t {
+            const CHANNELS: usize = 1;
+            const MAX_VALUE: f64 = $max;
+            type Diff = $diff;
+
+            #[inline(always)]
+            fn channel(&self, _: usize) -> f64 {
+                f64::from(*self)
+            }
+
+            #[inline(always)]
+            fn abs_diff(self, other: Self) -> $diff {
+                self.abs_diff(other)
+            }
+        }
+    )*};
+}
+
+impl_signed_channels! {i8 => u8, 255., i16 => u16, 65535., i32 => u32, 4294967295.}
+
+impl<T: PixelChannels, const N: usize> PixelChannels for [T; N] where T::Diff: Default {
+    const CHANNELS: usize = N * T::CHANNELS;
+    const MAX_VALUE: f64 = T::MAX_VALUE;
+    type Diff = [T::Diff; N];
+
+    #[inline]
+    fn channel(&self, index: usize) -> f64 {
+        self[index / T::CHANNELS].channel(index % T::CHANNELS)
+    }
+
+    #[inline]
+    fn abs_diff(self, other: Self) -> Self::Diff {
+        let mut diff = [T::Diff::default(); N];
+        for (d, (&a, &b)) in diff.iter_mut().zip(self.iter().zip(&other)) {
+            *d = a.abs_diff(b);
+        }
+        diff
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl<T: PixelChannels> PixelChannels for rgb::RGB<T> {
+    const CHANNELS: usize = 3 * T::CHANNELS;
+    const MAX_VALUE: f64 = T::MAX_VALUE;
+    type Diff = rgb::RGB<T::Diff>;
+
+    #[inline]
+    fn channel(&self, index: usize) -> f64 {
+        let px = [self.r, self.g, self.b];
+        px[index / T::CHANNELS].channel(index % T::CHANNELS)
+    }
+
+    #[inline]
+    fn abs_diff(self, other: Self) -> Self::Diff {
+        rgb::RGB {
+            r: self.r.abs_diff(other.r),
+            g: self.g.abs_diff(other.g),
+            b: self.b.abs_diff(other.b),
+        }
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl<T: PixelChannels> PixelChannels for rgb::RGBA<T> {
+    const CHANNELS: usize = 4 * T::CHANNELS;
+    const MAX_VALUE: f64 = T::MAX_VALUE;
+    type Diff = rgb::RGBA<T::Diff>;
+
+    #[inline]
+    fn channel(&self, index: usize) -> f64 {
+        let px = [self.r, self.g, self.b, self.a];
+        px[index / T::CHANNELS].channel(index % T::CHANNELS)
+    }
+
+    #[inline]
+    fn abs_diff(self, other: Self) -> Self::Diff {
+        rgb::RGBA {
+            r: self.r.abs_diff(other.r),
+            g: self.g.abs_diff(other.g),
+            b: self.b.abs_diff(other.b),
+            a: self.a.abs_diff(other.a),
+        }
+    }
+}
+
+/// Mean squared error, averaged over all channels of all pixels. It's 0 for identical (or empty) images.
+///
+/// # Panics
+///
+/// If the images have different sizes
+#[must_use]
+#[track_caller]
+pub fn mse<T: PixelChannels>(a: ImgRef<'_, T>, b: ImgRef<'_, T>) -> f64 {
+    check_size(a, b);
+    let mut sum = 0.;
+    for (row_a, row_b) in a.rows().zip(b.rows()) {
+        for (px_a, px_b) in row_a.iter().zip(row_b) {
+            for c in 0..T::CHANNELS {
+                let diff = px_a.channel(c) - px_b.channel(c);
+                sum += diff * diff;
+            }
+        }
+    }
+    let count = a.width() * a.height() * T::CHANNELS;
+    if count == 0 { 0. } else { sum / count as f64 }
+}
+
+/// Peak signal-to-noise ratio in dB, where `max_value` is the largest possible channel value (e.g. 255 for `u8`, 1.0 for normalized floats).
+///
+/// Higher is better. It's infinite for identical images.
+///
+/// # Panics
+///
+/// If the images have different sizes
+#[must_use]
+#[track_caller]
+pub fn psnr<T: PixelChannels>(a: ImgRef<'_, T>, b: ImgRef<'_, T>, max_value: f64) -> f64 {
+    let mse = mse(a, b);
+    if mse == 0. {
+        return f64::INFINITY;
+    }
+    10. * math::log10(max_value * max_value / mse)
+}
+
+/// The largest difference of any channel of any pixel. It's 0 for identical images.
+///
+/// # Panics
+///
+/// If the images have different sizes
+#[must_use]
+#[track_caller]
+pub fn max_abs_diff<T: PixelChannels>(a: ImgRef<'_, T>, b: ImgRef<'_, T>) -> f64 {
+    check_size(a, b);
+    let mut max = 0.;
+    for (row_a, row_b) in a.rows().zip(b.rows()) {
+        for (px_a, px_b) in row_a.iter().zip(row_b) {
+            for c in 0..T::CHANNELS {
+                let diff = math::abs(px_a.channel(c) - px_b.channel(c));
+                if diff > max {
+                    max = diff;
+                }
+            }
+        }
+    }
+    max
+}
+
+#[cfg(feature = "alloc")]
+/// Image of absolute differences between the pixels, channel by channel. It has no padding.
+///
+/// Differences of signed integers are unsigned (e.g. `u8` for `i8`), so they're never clamped.
+///
+/// # Panics
+///
+/// If the images have different sizes
+#[must_use]
+#[track_caller]
+pub fn diff_image<T: PixelChannels>(a: ImgRef<'_, T>, b: ImgRef<'_, T>) -> ImgVec<T::Diff> {
+    check_size(a, b);
+    let mut buf = Vec::with_capacity(a.width() * a.height());
+    for (row_a, row_b) in a.rows().zip(b.rows()) {
+        buf.extend(row_a.iter().zip(row_b).map(|(&px_a, &px_b)| px_a.abs_diff(px_b)));
+    }
+    Img::new_stride(buf, a.width(), a.height(), a.width().max(1))
+}
+
+/// Bounding box of all pixels that differ between the frames, or `None` if they're identical.
+///
+/// Useful for sending or encoding only the changed part of an animation frame.
+///
+/// # Panics
+///
+/// If the images have different sizes
+#[must_use]
+#[track_caller]
+pub fn changed_region<T: PartialEq>(prev: ImgRef<'_, T>, next: ImgRef<'_, T>) -> Option<Rect> {
+    check_size(prev, next);
+    let mut bounds = None;
+    for (y, (a, b)) in prev.rows().zip(next.rows()).enumerate() {
+        let first = match a.iter().zip(b).position(|(a, b)| a != b) {
+            Some(first) => first,
+            None => continue,
+        };
+        let last = a.iter().zip(b).rposition(|(a, b)| a != b).unwrap_or(first);
+        let (left, top, right, _) = bounds.unwrap_or((first, y, last, y));
+        bounds = Some((left.min(first), top, right.max(last), y));
+    }
+    let (left, top, right, bottom) = bounds?;
+    Some(Rect::new(left, top, right + 1 - left, bottom + 1 - top))
+}
+
+#[cfg(feature = "alloc")]
+/// Divides the frames into a grid of `tile`×`tile` squares, and returns the areas of the squares that have any different pixels.
+///
+/// Tiles are returned in order top to bottom, left to right. Tiles at the right and bottom edges may be smaller.
+///
+/// # Panics
+///
+/// If the images have different sizes, or if `tile` is 0
+#[must_use]
+#[track_caller]
+pub fn changed_tiles<T: PartialEq>(prev: ImgRef<'_, T>, next: ImgRef<'_, T>, tile: usize) -> Vec<Rect> {
+    check_size(prev, next);
+    assert!(tile > 0, "tile size must be > 0");
+    let (width, height) = (prev.width(), prev.height());
+    let mut changed = Vec::new();
+    for top in (0..height).step_by(tile) {
+        let tile_height = tile.min(height - top);
+        // one row of tiles, so that rows above it aren't walked again for every tile
+        let prev_band = prev.sub_image(0, top, width, tile_height);
+        let next_band = next.sub_image(0, top, width, tile_height);
+        for left in (0..width).step_by(tile) {
+            let rect = Rect::new(left, top, tile.min(width - left), tile_height);
+            let differs = prev_band.rows().zip(next_band.rows())
+                .any(|(a, b)| a[left..rect.right()] != b[left..rect.right()]);
+            if differs {
+                changed.push(rect);
+            }
+        }
+    }
+    changed
+}
+
+#[track_caller]
+fn check_size<T>(a: ImgRef<'_, T>, b: ImgRef<'_, T>) {
+    assert!(a.width() == b.width() && a.height() == b.height(),
+        "images must have the same size, but got {}×{} and {}×{}", a.width(), a.height(), b.width(), b.height());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn metrics() {
+    let a = Img::new_stride(vec![10u8, 20, 99, 30, 40], 2, 2, 3);
+    let b = Img::new(vec![10u8, 22, 26, 40], 2, 2);
+    assert_eq!(5., mse(a.as_ref(), b.as_ref()));
+    assert!((psnr(a.as_ref(), b.as_ref(), 255.) - 41.1411).abs() < 0.0001);
+    assert_eq!(f64::INFINITY, psnr(a.as_ref(), a.as_ref(), 255.));
+    assert_eq!(4., max_abs_diff(a.as_ref(), b.as_ref()));
+    assert_eq!(diff_image(a.as_ref(), b.as_ref()), Img::new(vec![0, 2, 4, 0], 2, 2));
+    assert_eq!(0., mse(a.sub_image(0, 0, 0, 0), b.sub_image(0, 0, 0, 0)));
+
+    let a = Img::new(vec![[1f32, 0.5], [0., 0.]], 2, 1);
+    let b = Img::new(vec![[1f32, 0.25], [0., 1.]], 2, 1);
+    assert_eq!(2, <[f32; 2]>::CHANNELS);
+    assert_eq!((0.0625 + 1.) / 4., mse(a.as_ref(), b.as_ref()));
+    assert_eq!(1., max_abs_diff(a.as_ref(), b.as_ref()));
+    assert_eq!(diff_image(a.as_ref(), b.as_ref()), Img::new(vec![[0., 0.25], [0., 1.]], 2, 1));
+
+    let a = Img::new(vec![-100i8, 5, i8::MIN], 3, 1);
+    let b = Img::new(vec![100i8, -5, i8::MAX], 3, 1);
+    assert_eq!(255., max_abs_diff(a.as_ref(), b.as_ref()));
+    assert_eq!(diff_image(a.as_ref(), b.as_ref()), Img::new(vec![200u8, 10, 255], 3, 1));
+    assert_eq!(u32::MAX, PixelChannels::abs_diff(i32::MIN, i32::MAX));
+    assert_eq!(3u16, PixelChannels::abs_diff(-1i16, 2));
+    assert_eq!([1u16, 0], PixelChannels::abs_diff([i16::MIN, 5], [i16::MIN + 1, 5]));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn changes() {
+    let prev = ImgVec::new_filled(10, 8, 0u8);
+    let mut next = Img::new_stride(vec![9u8; 13 * 8], 10, 8, 13);
+    next.pixels_mut().for_each(|px| *px = 0);
+    assert_eq!(None, changed_region(prev.as_ref(), next.as_ref()));
+    assert!(changed_tiles(prev.as_ref(), next.as_ref(), 4).is_empty());
+
+    next[(7usize, 2usize)] = 1;
+    next[(3usize, 5usize)] = 1;
+    assert_eq!(Some(Rect::new(3, 2, 5, 4)), changed_region(prev.as_ref(), next.as_ref()));
+    assert_eq!(vec![Rect::new(4, 0, 4, 4), Rect::new(0, 4, 4, 4)], changed_tiles(prev.as_ref(), next.as_ref(), 4));
+    next[(9usize, 7usize)] = 1;
+    assert_eq!(Rect::new(8, 4, 2, 4), changed_tiles(prev.as_ref(), next.as_ref(), 4)[2]);
+
+    let prev_sub = prev.sub_image(5, 1, 4, 3);
+    let next_sub = next.sub_image(5, 1, 4, 3);
+    assert_eq!(Some(Rect::new(2, 1, 1, 1)), changed_region(prev_sub, next_sub));
+    assert_eq!(vec![Rect::new(2, 0, 2, 2)], changed_tiles(prev_sub, next_sub, 2));
+}
+
+#[test]
+#[cfg(all(feature = "alloc", feature = "rgb"))]
+fn rgb_metrics() {
+    use rgb::{RGB8, RGBA};
+    let a = Img::new(vec![RGB8::new(1, 2, 3)], 1, 1);
+    let b = Img::new(vec![RGB8::new(3, 2, 1)], 1, 1);
+    assert_eq!(8. / 3., mse(a.as_ref(), b.as_ref()));
+    assert_eq!(diff_image(a.as_ref(), b.as_ref()), Img::new(vec![RGB8::new(2, 0, 2)], 1, 1));
+    let a = Img::new(vec![RGBA::new(0u16, 0, 0, 100)], 1, 1);
+    let b = Img::new(vec![RGBA::new(0u16, 0, 0, 0)], 1, 1);
+    assert_eq!(100., max_abs_diff(a.as_ref(), b.as_ref()));
+    let a = Img::new(vec![rgb::RGB::new(-128i8, 0, 1)], 1, 1);
+    let b = Img::new(vec![rgb::RGB::new(127i8, 0, -1)], 1, 1);
+    assert_eq!(diff_image(a.as_ref(), b.as_ref()), Img::new(vec![RGB8::new(255, 0, 2)], 1, 1));
+}
@@ -0,0 +1,284 @@
+use super::{check_size, PixelChannels};
+use crate::math;
+use crate::{Img, ImgRef, ImgVec};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Weights of the scales in MS-SSIM, from the finest to the coarsest
+const MS_SSIM_WEIGHTS: [f64; 5] = [0.0448, 0.2856, 0.3001, 0.2363, 0.1333];
+
+/// Radius of the Gaussian window (11×11, σ=1.5)
+const RADIUS: usize = 5;
+const SIGMA: f64 = 1.5;
+
+/// Structural similarity index of two images. Higher is better, and 1.0 means the images are identical.
+///
+/// Uses an 11×11 Gaussian window. Channels are compared separately and the result is their average.
+/// The range of channel values is taken from [`PixelChannels::MAX_VALUE`] (e.g. 255 for `u8`, 1.0 for `f64`).
+///
+/// # Panics
+///
+/// If the images have different sizes
+#[must_use]
+#[track_caller]
+pub fn ssim<T: PixelChannels>(a: ImgRef<'_, T>, b: ImgRef<'_, T>) -> f64 {
+    check_size(a, b);
+    let (score, _) = ssim_channels(a, b, false);
+    score
+}
+
+/// Same as [`ssim()`], but also returns a map of local similarity at every pixel (the score is the map's average).
+///
+/// # Panics
+///
+/// If the images have different sizes
+#[must_use]
+#[track_caller]
+pub fn ssim_with_map<T: PixelChannels>(a: ImgRef<'_, T>, b: ImgRef<'_, T>) -> (f64, ImgVec<f32>) {
+    check_size(a, b);
+    let (score, map) = ssim_channels(a, b, true);
+    let width = a.width();
+    (score, Img::new_stride(map, width, a.height(), width.max(1)))
+}
+
+/// Multi-scale structural similarity index. Higher is better, and 1.0 means the images are identical.
+///
+/// Compares the images at 5 scales, halving the size each time. Images smaller than 176 pixels use fewer scales
+/// (and for images under 22 pixels it's the same as [`ssim()`]).
+///
+/// With more than one scale, negative similarity at any scale (e.g. of inverted images) is clamped to 0, because it can't be weighted.
+///
+/// # Panics
+///
+/// If the images have different sizes
+#[must_use]
+#[track_caller]
+pub fn ms_ssim<T: PixelChannels>(a: ImgRef<'_, T>, b: ImgRef<'_, T>) -> f64 {
+    check_size(a, b);
+    let mut width = a.width();
+    let mut height = a.height();
+    if width == 0 || height == 0 {
+        return 1.;
+    }
+    let mut scales = 1;
+    while scales < MS_SSIM_WEIGHTS.len() && (width.min(height) >> scales) > 2 * RADIUS {
+        scales += 1;
+    }
+    if scales == 1 {
+        return ssim_channels(a, b, false).0;
+    }
+    let weights = &MS_SSIM_WEIGHTS[..scales];
+    let weight_sum: f64 = weights.iter().sum();
+
+    let (c1, c2) = constants::<T>();
+    let mut total = 0.;
+    for c in 0..T::CHANNELS {
+        let mut x = plane(a, c);
+        let mut y = plane(b, c);
+        let mut score = 1.;
+        for (scale, &weight) in weights.iter().enumerate() {
+            let stats = Stats::new(&x, &y, width, height);
+            let last = scale + 1 == scales;
+            let mut sum = 0.;
+            for i in 0..x.len() {
+                let (luminance, contrast_structure) = stats.terms(i, c1, c2);
+                sum += if last { luminance * contrast_structure } else { contrast_structure };
+            }
+            let mean = sum / x.len() as f64;
+            score *= math::powf(mean.max(0.), weight / weight_sum);
+            if !last {
+                x = downsample(&x, width, height);
+                y = downsample(&y, width, height);
+                width /= 2;
+                height /= 2;
+            }
+        }
+        total += score;
+    }
+    total / T::CHANNELS as f64
+}
+
+/// Returns the average score, and the map averaged over channels if `want_map`
+fn ssim_channels<T: PixelChannels>(a: ImgRef<'_, T>, b: ImgRef<'_, T>, want_map: bool) -> (f64, Vec<f32>) {
+    let width = a.width();
+    let height = a.height();
+    let len = width * height;
+    let mut map = if want_map { vec![0.; len] } else { Vec::new() };
+    if len == 0 {
+        return (1., map);
+    }
+    let (c1, c2) = constants::<T>();
+    let mut sum = 0.;
+    for c in 0..T::CHANNELS {
+        let stats = Stats::new(&plane(a, c), &plane(b, c), width, height);
+        for i in 0..len {
+            let (luminance, contrast_structure) = stats.terms(i, c1, c2);
+            let ssim = luminance * contrast_structure;
+            sum += ssim;
+            if let Some(m) = map.get_mut(i) {
+                *m += (ssim / T::CHANNELS as f64) as f32;
+            }
+        }
+    }
+    (sum / (len * T::CHANNELS) as f64, map)
+}
+
+fn constants<T: PixelChannels>() -> (f64, f64) {
+    let k1 = 0.01 * T::MAX_VALUE;
+    let k2 = 0.03 * T::MAX_VALUE;
+    (k1 * k1, k2 * k2)
+}
+
+/// Locally weighted means, variances and covariance of two planes.
+///
+/// They're in `f64`, because variance is computed as E[x²] - E[x]², which loses precision for large channel values.
+struct Stats {
+    mu_x: Vec<f64>,
+    mu_y: Vec<f64>,
+    xx: Vec<f64>,
+    yy: Vec<f64>,
+    xy: Vec<f64>,
+}
+
+impl Stats {
+    fn new(x: &[f64], y: &[f64], width: usize, height: usize) -> Self {
+        let kernel = kernel();
+        let blurred = |f: &dyn Fn(usize) -> f64| {
+            let src: Vec<f64> = (0..x.len()).map(f).collect();
+            blur(&src, width, height, &kernel)
+        };
+        Self {
+            mu_x: blurred(&|i| x[i]),
+            mu_y: blurred(&|i| y[i]),
+            xx: blurred(&|i| x[i] * x[i]),
+            yy: blurred(&|i| y[i] * y[i]),
+            xy: blurred(&|i| x[i] * y[i]),
+        }
+    }
+
+    /// Luminance and contrast-structure terms of SSIM at pixel `i`
+    #[inline]
+    fn terms(&self, i: usize, c1: f64, c2: f64) -> (f64, f64) {
+        let mu_x = self.mu_x[i];
+        let mu_y = self.mu_y[i];
+        let mu_xy = mu_x * mu_y;
+        let mu_xx = mu_x * mu_x;
+        let mu_yy = mu_y * mu_y;
+        let var_x = self.xx[i] - mu_xx;
+        let var_y = self.yy[i] - mu_yy;
+        let cov = self.xy[i] - mu_xy;
+        let luminance = (2. * mu_xy + c1) / (mu_xx + mu_yy + c1);
+        let contrast_structure = (2. * cov + c2) / (var_x + var_y + c2);
+        (luminance, contrast_structure)
+    }
+}
+
+fn kernel() -> [f64; 2 * RADIUS + 1] {
+    let mut kernel = [0.; 2 * RADIUS + 1];
+    let mut sum = 0.;
+    for (i, k) in kernel.iter_mut().enumerate() {
+        let d = i as f64 - RADIUS as f64;
+        *k = math::exp(-d * d / (2. * SIGMA * SIGMA));
+        sum += *k;
+    }
+    for k in &mut kernel {
+        *k /= sum;
+    }
+    kernel
+}
+
+/// Separable Gaussian blur, with edge pixels extended beyond the edges of the image
+fn blur(src: &[f64], width: usize, height: usize, kernel: &[f64; 2 * RADIUS + 1]) -> Vec<f64> {
+    let clamp = |pos: usize, offset: usize, size: usize| (pos + offset).saturating_sub(RADIUS).min(size - 1);
+    let mut tmp = vec![0.; src.len()];
+    for (src_row, tmp_row) in src.chunks_exact(width).zip(tmp.chunks_exact_mut(width)) {
+        for (x, out) in tmp_row.iter_mut().enumerate() {
+            *out = kernel.iter().enumerate().map(|(k, w)| w * src_row[clamp(x, k, width)]).sum();
+        }
+    }
+    let mut out = vec![0.; src.len()];
+    for (y, out_row) in out.chunks_exact_mut(width).enumerate() {
+        for (k, w) in kernel.iter().enumerate() {
+            let tmp_row = &tmp[clamp(y, k, height) * width..][..width];
+            for (out, tmp) in out_row.iter_mut().zip(tmp_row) {
+                *out += w * tmp;
+            }
+        }
+    }
+    out
+}
+
+/// Halve the size by averaging 2×2 pixels
+fn downsample(src: &[f64], width: usize, height: usize) -> Vec<f64> {
+    let (half_width, half_height) = (width / 2, height / 2);
+    let mut out = Vec::with_capacity(half_width * half_height);
+    for y in 0..half_height {
+        let top = &src[2 * y * width..][..width];
+        let bottom = &src[(2 * y + 1) * width..][..width];
+        out.extend((0..half_width).map(|x| (top[2 * x] + top[2 * x + 1] + bottom[2 * x] + bottom[2 * x + 1]) * 0.25));
+    }
+    out
+}
+
+/// One channel of the image as a contiguous buffer, without padding
+fn plane<T: PixelChannels>(img: ImgRef<'_, T>, channel: usize) -> Vec<f64> {
+    let mut out = Vec::with_capacity(img.width() * img.height());
+    for row in img.rows() {
+        out.extend(row.iter().map(|px| px.channel(channel)));
+    }
+    out
+}
+
+#[test]
+fn ssim_scores() {
+    use crate::patterns;
+
+    let a = patterns::noise(64, 48, 1);
+    let padded = patterns::padded(a.as_ref(), 3, 0);
+    assert!((ssim(a.as_ref(), padded.as_ref()) - 1.).abs() < 1e-6);
+    assert!((ms_ssim(a.as_ref(), padded.as_ref()) - 1.).abs() < 1e-6);
+
+    let unrelated = patterns::noise(64, 48, 2);
+    let s = ssim(a.as_ref(), unrelated.as_ref());
+    assert!(s.abs() < 0.1, "{s}");
+    assert_eq!(s, ssim(unrelated.as_ref(), a.as_ref()));
+
+    let noisy = ImgVec::from_fn(64, 48, |x, y| a[(x, y)].saturating_add(if (x + y) % 2 == 0 { 8 } else { 0 }));
+    let (s, map) = ssim_with_map(a.as_ref(), noisy.as_ref());
+    assert!(s > 0.9 && s < 1., "{s}");
+    assert_eq!((64, 48), (map.width(), map.height()));
+    let map_mean = map.pixels().map(f64::from).sum::<f64>() / (64. * 48.);
+    assert!((map_mean - s).abs() < 1e-5);
+
+    let big = patterns::noise(200, 180, 3);
+    let noisy_big = ImgVec::from_fn(200, 180, |x, y| big[(x, y)].saturating_add(if (x * 3 + y) % 4 == 0 { 40 } else { 0 }));
+    let s = ms_ssim(big.as_ref(), noisy_big.as_ref());
+    assert!(s > 0.5 && s < 1., "{s}");
+
+    let a = Img::new(vec![[0.5f32, 0.5, 0.5]; 16 * 16], 16, 16);
+    let b = Img::new(vec![[0.5f32, 0.5, 0.6]; 16 * 16], 16, 16);
+    let s = ssim(a.as_ref(), b.as_ref());
+    assert!(s > 0.9 && s < 1., "{s}");
+
+    // shifting bright pixels barely changes the score, unless variances lose precision
+    let noise = patterns::noise(32, 32, 4);
+    let other = patterns::noise(32, 32, 5);
+    let score = |offset: u16| {
+        let a = ImgVec::from_fn(32, 32, |x, y| offset + u16::from(noise[(x, y)]));
+        let b = ImgVec::from_fn(32, 32, |x, y| offset + u16::from(noise[(x, y)] / 2 + other[(x, y)] / 2));
+        ssim(a.as_ref(), b.as_ref())
+    };
+    let (s1, s2) = (score(0xFE00), score(0xFF00));
+    assert!((s1 - s2).abs() < 1e-7, "{s1} {s2}");
+
+    // single-scale ms_ssim isn't clamped, and multi-scale is
+    let checkers = ImgVec::from_fn(8, 8, |x, y| if (x + y) % 2 == 0 { 255u8 } else { 0 });
+    let inverted = ImgVec::from_fn(8, 8, |x, y| 255 - checkers[(x, y)]);
+    let s = ssim(checkers.as_ref(), inverted.as_ref());
+    assert!(s < -0.9, "{s}");
+    assert_eq!(s, ms_ssim(checkers.as_ref(), inverted.as_ref()));
+    let checkers = ImgVec::from_fn(64, 64, |x, y| if (x + y) % 2 == 0 { 255u8 } else { 0 });
+    let inverted = ImgVec::from_fn(64, 64, |x, y| 255 - checkers[(x, y)]);
+    assert!(ssim(checkers.as_ref(), inverted.as_ref()) < 0.);
+    assert_eq!(0., ms_ssim(checkers.as_ref(), inverted.as_ref()));
+}
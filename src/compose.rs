@@ -0,0 +1,125 @@
+use crate::{Img, ImgError, ImgRef, ImgRefMut, ImgVec};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Join images of the same width vertically, top to bottom.
+///
+/// # Panics
+///
+/// If the images have different widths. See [`try_vstack()`].
+#[must_use]
+#[track_caller]
+pub fn vstack<T: Copy>(images: &[ImgRef<'_, T>]) -> ImgVec<T> {
+    match try_vstack(images) {
+        Ok(img) => img,
+        Err(err) => panic!("can't vstack images: {err}"),
+    }
+}
+
+/// Join images of the same width vertically, top to bottom.
+///
+/// Returns an error if the images have different widths.
+pub fn try_vstack<T: Copy>(images: &[ImgRef<'_, T>]) -> Result<ImgVec<T>, ImgError> {
+    let width = images.first().map_or(0, |img| img.width());
+    if let Some(img) = images.iter().find(|img| img.width() != width) {
+        return Err(ImgError::WidthMismatch { expected: width, found: img.width() });
+    }
+    let height = images.iter().map(|img| img.height()).sum();
+    let mut buf = Vec::with_capacity(width * height);
+    for img in images {
+        for row in img.rows() {
+            buf.extend_from_slice(row);
+        }
+    }
+    Ok(Img::new_stride(buf, width, height, width.max(1)))
+}
+
+/// Join images of the same height horizontally, left to right.
+///
+/// # Panics
+///
+/// If the images have different heights. See [`try_hstack()`].
+#[must_use]
+#[track_caller]
+pub fn hstack<T: Copy>(images: &[ImgRef<'_, T>]) -> ImgVec<T> {
+    match try_hstack(images) {
+        Ok(img) => img,
+        Err(err) => panic!("can't hstack images: {err}"),
+    }
+}
+
+/// Join images of the same height horizontally, left to right.
+///
+/// Returns an error if the images have different heights.
+pub fn try_hstack<T: Copy>(images: &[ImgRef<'_, T>]) -> Result<ImgVec<T>, ImgError> {
+    let height = images.first().map_or(0, |img| img.height());
+    if let Some(img) = images.iter().find(|img| img.height() != height) {
+        return Err(ImgError::HeightMismatch { expected: height, found: img.height() });
+    }
+    let width = images.iter().map(|img| img.width()).sum();
+    let mut buf = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for img in images {
+            buf.extend_from_slice(&img[y]);
+        }
+    }
+    Ok(Img::new_stride(buf, width, height, width.max(1)))
+}
+
+/// Arrange images in a grid with `columns` cells per row (e.g. for a contact sheet or a side-by-side comparison).
+///
+/// All cells have the size of the largest image, and are `spacing` pixels apart.
+/// Images are placed in the top-left corner of their cells, and the remaining area is filled with `background`.
+///
+/// # Panics
+///
+/// If `columns` is 0
+#[must_use]
+#[track_caller]
+pub fn grid<T: Copy>(images: &[ImgRef<'_, T>], columns: usize, spacing: usize, background: T) -> ImgVec<T> {
+    assert!(columns > 0);
+    let columns = columns.min(images.len());
+    let rows = if images.is_empty() { 0 } else { (images.len() + columns - 1) / columns };
+    let cell_width = images.iter().map(|img| img.width()).max().unwrap_or(0);
+    let cell_height = images.iter().map(|img| img.height()).max().unwrap_or(0);
+    let width = (columns * (cell_width + spacing)).saturating_sub(spacing);
+    let height = (rows * (cell_height + spacing)).saturating_sub(spacing);
+    let stride = width.max(1);
+    let mut out = Img::new_stride(vec![background; stride * height], width, height, stride);
+    for (i, img) in images.iter().enumerate() {
+        let left = (i % columns) * (cell_width + spacing);
+        let top = (i / columns) * (cell_height + spacing);
+        copy_into(out.sub_image_mut(left, top, img.width(), img.height()), *img);
+    }
+    out
+}
+
+/// Copy pixels of `src` to `dst` of the same size
+#[track_caller]
+pub(crate) fn copy_into<T: Copy>(mut dst: ImgRefMut<'_, T>, src: ImgRef<'_, T>) {
+    debug_assert_eq!((dst.width(), dst.height()), (src.width(), src.height()));
+    for (dst_row, src_row) in dst.rows_mut().zip(src.rows()) {
+        dst_row.copy_from_slice(src_row);
+    }
+}
+
+#[test]
+fn stacking() {
+    let a = Img::new_stride(vec![1, 2, 0, 3, 4], 2, 2, 3);
+    let b = Img::new(vec![5, 6], 2, 1);
+    let c = Img::new(vec![7, 8], 1, 2);
+
+    assert_eq!(vstack(&[a.as_ref(), b.as_ref()]), Img::new(vec![1, 2, 3, 4, 5, 6], 2, 3));
+    assert_eq!(hstack(&[a.as_ref(), c.as_ref()]), Img::new(vec![1, 2, 7, 3, 4, 8], 3, 2));
+    assert_eq!(Err(ImgError::WidthMismatch { expected: 2, found: 1 }), try_vstack(&[a.as_ref(), c.as_ref()]));
+    assert_eq!(Err(ImgError::HeightMismatch { expected: 2, found: 1 }), try_hstack(&[a.as_ref(), b.as_ref()]));
+    assert_eq!(0, vstack::<u8>(&[]).height());
+    assert_eq!(0, grid::<u8>(&[], 3, 1, 0).width());
+
+    let sheet = grid(&[a.as_ref(), b.as_ref(), c.as_ref()], 2, 1, 0);
+    assert_eq!(sheet, Img::new(vec![1, 2, 0, 5, 6,
+                                    3, 4, 0, 0, 0,
+                                    0, 0, 0, 0, 0,
+                                    7, 0, 0, 0, 0,
+                                    8, 0, 0, 0, 0], 5, 5));
+}
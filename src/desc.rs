@@ -0,0 +1,105 @@
+use crate::{ImgError, ImgRef, ImgRefMut};
+use core::ffi::c_void;
+use core::mem;
+
+/// Description of an image in memory, with a C-compatible layout, for passing images between Rust and C.
+///
+/// Row `y` starts `y * stride_bytes` bytes after `data`, and has `width` pixels of `elem_size` bytes each.
+/// The descriptor doesn't own the pixels, and doesn't keep track of their type or lifetime.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ImgDesc {
+    /// Address of the first (top left) pixel
+    pub data: *mut c_void,
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+    /// Distance between starts of rows, in bytes
+    pub stride_bytes: usize,
+    /// Size of one pixel in bytes
+    pub elem_size: usize,
+}
+
+impl ImgDesc {
+    fn new<T>(data: *mut T, width: usize, height: usize, stride: usize) -> Result<Self, ImgError> {
+        let too_large = ImgError::TooLarge { width, height };
+        Ok(Self {
+            data: data.cast(),
+            width: width.try_into().map_err(|_| too_large.clone())?,
+            height: height.try_into().map_err(|_| too_large.clone())?,
+            stride_bytes: stride.checked_mul(mem::size_of::<T>()).ok_or(too_large)?,
+            elem_size: mem::size_of::<T>(),
+        })
+    }
+
+    /// Borrow the described pixels as an image. It's the opposite of `ImgDesc::try_from(img)`.
+    ///
+    /// Returns an error if `elem_size` isn't the size of `T`, or the descriptor is invalid (see [`ImgRef::from_raw_parts()`]).
+    ///
+    /// # Safety
+    ///
+    /// The descriptor must point to initialized pixels of type `T` that are valid for reads and not mutated for the lifetime `'a`.
+    pub unsafe fn as_img_ref<'a, T>(&self) -> Result<ImgRef<'a, T>, ImgError> {
+        self.check_elem_size::<T>()?;
+        ImgRef::from_raw_parts(self.data.cast::<T>(), self.width as usize, self.height as usize, self.stride_bytes)
+    }
+
+    /// Borrow the described pixels as a mutable image. See [`as_img_ref()`](Self::as_img_ref).
+    ///
+    /// # Safety
+    ///
+    /// The descriptor must point to initialized pixels of type `T` that are valid for reads and writes,
+    /// and not accessed through any other pointer for the lifetime `'a`.
+    pub unsafe fn as_img_ref_mut<'a, T>(&self) -> Result<ImgRefMut<'a, T>, ImgError> {
+        self.check_elem_size::<T>()?;
+        ImgRefMut::from_raw_parts_mut(self.data.cast::<T>(), self.width as usize, self.height as usize, self.stride_bytes)
+    }
+
+    fn check_elem_size<T>(&self) -> Result<(), ImgError> {
+        if self.elem_size != mem::size_of::<T>() {
+            return Err(ImgError::PixelSizeMismatch { expected: mem::size_of::<T>(), found: self.elem_size });
+        }
+        Ok(())
+    }
+}
+
+impl<T> TryFrom<ImgRef<'_, T>> for ImgDesc {
+    type Error = ImgError;
+
+    /// Fails if the dimensions don't fit in `u32`. The pixels must not be written to through the descriptor.
+    #[inline]
+    fn try_from(img: ImgRef<'_, T>) -> Result<Self, ImgError> {
+        Self::new(img.buf().as_ptr() as *mut T, img.width(), img.height(), img.stride())
+    }
+}
+
+impl<T> TryFrom<ImgRefMut<'_, T>> for ImgDesc {
+    type Error = ImgError;
+
+    /// Fails if the dimensions don't fit in `u32`
+    #[inline]
+    fn try_from(mut img: ImgRefMut<'_, T>) -> Result<Self, ImgError> {
+        let (width, height, stride) = (img.width(), img.height(), img.stride());
+        Self::new(img.buf_mut().as_mut_ptr(), width, height, stride)
+    }
+}
+
+#[test]
+fn desc() {
+    use crate::Img;
+
+    let mut pixels = [1u16, 2, 3, 0, 4, 5, 6];
+    let img = Img::new_stride(&pixels[..], 3, 2, 4);
+    let desc = ImgDesc::try_from(img).unwrap();
+    assert_eq!((3, 2, 8, 2), (desc.width, desc.height, desc.stride_bytes, desc.elem_size));
+    assert_eq!(img, unsafe { desc.as_img_ref::<u16>() }.unwrap());
+    assert_eq!(Some(ImgError::PixelSizeMismatch { expected: 4, found: 2 }), unsafe { desc.as_img_ref::<u32>() }.err());
+
+    let desc = ImgDesc::try_from(Img::new_stride(&mut pixels[1..], 2, 2, 4)).unwrap();
+    unsafe { desc.as_img_ref_mut::<u16>() }.unwrap()[(1usize, 1usize)] = 60;
+    assert_eq!(60, pixels[6]);
+
+    let desc = ImgDesc { data: core::ptr::null_mut(), ..desc };
+    assert_eq!(Some(ImgError::NullPointer), unsafe { desc.as_img_ref::<u16>() }.err());
+}
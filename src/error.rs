@@ -0,0 +1,79 @@
+use core::fmt;
+
+/// Error returned by the fallible (`try_`) methods
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ImgError {
+    /// Images that were supposed to have the same width don't
+    WidthMismatch {
+        /// The width of the first image
+        expected: usize,
+        /// The width of the mismatched image
+        found: usize,
+    },
+    /// Images that were supposed to have the same height don't
+    HeightMismatch {
+        /// The height of the first image
+        expected: usize,
+        /// The height of the mismatched image
+        found: usize,
+    },
+    /// A row given to a builder doesn't have the width of the image
+    RowLengthMismatch {
+        /// Index of the row in the image
+        row: usize,
+        /// The width of the image
+        expected: usize,
+        /// Length of the row
+        found: usize,
+    },
+    /// The number of pixels doesn't fit in `usize`, or a dimension is `u32::MAX` or larger
+    TooLarge {
+        /// Requested width
+        width: usize,
+        /// Requested height
+        height: usize,
+    },
+    /// Memory for the pixels couldn't be allocated
+    AllocationFailed {
+        /// Number of pixels that were requested
+        pixels: usize,
+    },
+    /// Distance between rows isn't a whole number of pixels, or is less than the width
+    InvalidStride {
+        /// Stride in bytes
+        stride_bytes: usize,
+        /// Size of one pixel in bytes
+        pixel_size: usize,
+    },
+    /// The pointer to the pixels is null
+    NullPointer,
+    /// Size of pixels in an [`ImgDesc`](crate::ImgDesc) doesn't match the pixel type
+    PixelSizeMismatch {
+        /// Size of the pixel type
+        expected: usize,
+        /// Element size in the descriptor
+        found: usize,
+    },
+    /// The requested alignment isn't a power of two, or a pointer isn't aligned for the pixel type
+    Unaligned {
+        /// Required alignment in bytes
+        align: usize,
+    },
+}
+
+impl fmt::Display for ImgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::WidthMismatch { expected, found } => write!(f, "expected width {expected}, got {found}"),
+            Self::HeightMismatch { expected, found } => write!(f, "expected height {expected}, got {found}"),
+            Self::RowLengthMismatch { row, expected, found } => write!(f, "row {row} has {found} pixels, but the width is {expected}"),
+            Self::TooLarge { width, height } => write!(f, "image size {width}×{height} is too large"),
+            Self::AllocationFailed { pixels } => write!(f, "can't allocate memory for {pixels} pixels"),
+            Self::InvalidStride { stride_bytes, pixel_size } => write!(f, "stride of {stride_bytes} bytes is invalid for {pixel_size}-byte pixels"),
+            Self::NullPointer => f.write_str("pointer to the pixels is null"),
+            Self::PixelSizeMismatch { expected, found } => write!(f, "expected {expected}-byte pixels, got {found}-byte elements"),
+            Self::Unaligned { align } => write!(f, "alignment of {align} bytes is invalid or isn't met"),
+        }
+    }
+}
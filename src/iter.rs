@@ -3,7 +3,7 @@ use core::marker::PhantomData;
 use core::num::NonZeroUsize;
 use core::slice;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 use alloc::vec;
 
 /// Rows of the image. Call `Img.rows()` to create it.
@@ -342,6 +342,7 @@ impl<T: Copy> ExactSizeIterator for PixelsIterMut<'_, T> {
 }
 
 #[test]
+#[cfg(feature = "alloc")]
 fn iter() {
     let img = super::Img::new(vec![1u8, 2], 1, 2);
     let mut it = img.pixels();
@@ -19,8 +19,13 @@
 //! It is assumed that the container is [one element per pixel](https://crates.io/crates/rgb/), e.g. `Vec<RGBA>`,
 //! and _not_ a `Vec<u8>` where 4 `u8` elements are interpreted as one pixel.
 //!
+//! The crate is `no_std`. Everything that needs a heap, including `ImgVec`, is behind the default `alloc` feature.
+//! Without it, `ImgRef`, `ImgRefMut` and `ImgArray` still work, e.g. for static framebuffers.
+//!
+//! Images can be passed to and from C as an [`ImgDesc`]. The optional `capi` feature exports C functions for them.
 //!
 //!  ```rust
+//!  # #[cfg(feature = "alloc")] {
 //!  use imgref::*;
 //!  # fn some_image_processing_function(img: ImgRef<u8>) -> ImgVec<u8> { img.new_buf(img.buf().to_vec()) }
 //!
@@ -44,29 +49,113 @@
 //!  let fragment = img.sub_image(5, 5, 15, 15);
 //!
 //!  let (vec, width, height) = fragment.to_contiguous_buf();
+//!  # }
 //!  ```
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
-#[cfg(test)]
+// the C API is linked into C programs, which need std's panic handler and allocator
+#[cfg(any(test, feature = "capi"))]
 extern crate std;
 
+#[cfg(feature = "alloc")]
 use alloc::borrow::{Cow, ToOwned};
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
+use core::mem;
+#[cfg(feature = "deprecated")]
 use core::slice;
 
 mod traits;
 
 mod iter;
 mod ops;
+mod transform;
 pub use iter::*;
 
+mod orientation;
+pub use orientation::*;
+
+mod rect;
+pub use rect::Rect;
+
+#[cfg(feature = "alloc")]
+mod canvas;
+
+mod blocks;
+pub use blocks::*;
+
+mod tiles;
+pub use tiles::*;
+
+mod error;
+pub use error::ImgError;
+
+#[cfg(feature = "alloc")]
+mod compose;
+#[cfg(feature = "alloc")]
+pub use compose::*;
+
+#[cfg(feature = "alloc")]
+mod atlas;
+#[cfg(feature = "alloc")]
+pub use atlas::*;
+
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+pub use builder::ImgVecBuilder;
+
+mod trim;
+pub use trim::*;
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+mod arc;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub use arc::{ArcSlice, ImgArc};
+
+#[cfg(feature = "alloc")]
+mod offset;
+#[cfg(feature = "alloc")]
+pub use offset::{ImgVecOffset, OffsetVec};
+
+#[cfg(feature = "alloc")]
+mod aligned;
+#[cfg(feature = "alloc")]
+pub use aligned::{AlignedBuf, ImgVecAligned};
+
+#[cfg(feature = "alloc")]
+mod uninit;
+
+mod array;
+pub use array::ImgArray;
+
+mod raw;
+
+mod desc;
+pub use desc::ImgDesc;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+
+#[cfg(feature = "alloc")]
+pub mod patterns;
+
+pub mod compare;
+mod math;
+
+mod testing;
+#[doc(hidden)]
+pub use testing::{__approx_eq, __assert_img_eq};
+
 /// Image owning its pixels.
 ///
 /// A 2D array of pixels. The pixels are oriented top-left first and rows are `stride` pixels wide.
 ///
 /// If size of the `buf` is larger than `width`*`height`, then any excess space is a padding (see `width_padded()`/`height_padded()`).
+#[cfg(feature = "alloc")]
 pub type ImgVec<Pixel> = Img<Vec<Pixel>>;
 
 /// Reference to pixels inside another image.
@@ -113,6 +202,56 @@ pub trait ImgExt<Pixel> {
 
     /// Borrow the container
     fn as_ref(&self) -> ImgRef<'_, Pixel>;
+
+    /// Iterate over rows of the image as slices, each exactly `width` pixels wide
+    ///
+    /// Same as [`ImgRef::rows()`], for images in any container.
+    #[inline]
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn rows(&self) -> RowsIter<'_, Pixel> {
+        self.as_ref().rows()
+    }
+
+    /// Iterate `width*height` pixels in the image, ignoring padding area
+    ///
+    /// Same as [`ImgRef::pixels()`], for images in any container.
+    #[inline]
+    fn pixels(&self) -> PixelsIter<'_, Pixel> where Pixel: Copy {
+        PixelsIter::new(self.as_ref())
+    }
+
+    /// Iterate `width*height` pixels in the image, by reference, ignoring padding area
+    ///
+    /// Same as [`ImgRef::pixels_ref()`], for images in any container.
+    #[inline]
+    fn pixels_ref(&self) -> PixelsRefIter<'_, Pixel> {
+        PixelsRefIter::new(self.as_ref())
+    }
+
+    /// Make a reference for a part of the image, without copying any pixels.
+    ///
+    /// # Panics
+    ///
+    /// If the coordinates are out of bounds
+    #[inline]
+    #[track_caller]
+    fn sub_image(&self, left: usize, top: usize, width: usize, height: usize) -> ImgRef<'_, Pixel> {
+        self.as_ref().sub_image(left, top, width, height)
+    }
+
+    /// The largest power of two (in bytes) that the address of every row's first pixel is a multiple of.
+    ///
+    /// SIMD code can use aligned loads if it's at least the size of a vector.
+    #[cfg_attr(feature = "alloc", doc = "See [`ImgVecAligned::try_new_byte_aligned()`] for making images with aligned rows.")]
+    #[inline]
+    fn row_alignment(&self) -> usize {
+        let img = self.as_ref();
+        let mut bits = img.buf().as_ptr() as usize;
+        if img.height() > 1 {
+            bits |= img.stride() * mem::size_of::<Pixel>();
+        }
+        bits & bits.wrapping_neg()
+    }
 }
 
 /// Additional methods that depend on buffer size
@@ -131,6 +270,40 @@ pub trait ImgExtMut<Pixel> {
 
     /// Borrow the container mutably
     fn as_mut(&mut self) -> ImgRefMut<'_, Pixel>;
+
+    /// Iterate over rows of the image as mutable slices, each exactly `width` pixels wide
+    ///
+    /// Same as [`ImgRefMut::rows_mut()`], for images in any container.
+    #[inline]
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn rows_mut(&mut self) -> RowsIterMut<'_, Pixel> {
+        let img = self.as_mut();
+        let stride = img.stride();
+        let len = img.as_ref().valid_min_len();
+        RowsIterMut {
+            width: img.width(),
+            inner: img.into_buf()[..len].chunks_mut(stride),
+        }
+    }
+
+    /// Iterate `width*height` pixels in the image mutably, ignoring padding area
+    ///
+    /// Same as [`ImgRefMut::pixels_mut()`], for images in any container.
+    #[inline]
+    fn pixels_mut(&mut self) -> PixelsIterMut<'_, Pixel> {
+        PixelsIterMut::new(self.as_mut())
+    }
+
+    /// Make a mutable reference for a part of the image, without copying any pixels.
+    ///
+    /// # Panics
+    ///
+    /// If the coordinates are out of bounds
+    #[inline]
+    #[track_caller]
+    fn sub_image_mut(&mut self, left: usize, top: usize, width: usize, height: usize) -> ImgRefMut<'_, Pixel> {
+        self.as_mut().into_sub_image_mut(left, top, width, height)
+    }
 }
 
 /// Basic struct used for both owned (alias `ImgVec`) and borrowed (alias `ImgRef`) image fragments.
@@ -202,20 +375,21 @@ impl<Container> Img<Container> {
     pub const fn stride(&self) -> usize { self.stride }
 
     /// Immutable reference to the pixel storage. Warning: exposes stride. Use [`pixels()`](Self::pixels) or [`rows()`](Self::rows) instead.
-    ///
-    /// See also [`into_contiguous_buf()`](Self::into_contiguous_buf).
+    #[cfg_attr(feature = "alloc", doc = "")]
+    #[cfg_attr(feature = "alloc", doc = "See also [`into_contiguous_buf()`](Self::into_contiguous_buf).")]
     #[inline(always)]
     #[allow(deprecated)]
     pub const fn buf(&self) -> &Container { &self.buf }
 
     /// Mutable reference to the pixel storage. Warning: exposes stride. Use [`pixels_mut()`](Self::pixels_mut) or [`rows_mut()`](Self::rows_mut) instead.
-    ///
-    /// See also [`into_contiguous_buf()`](Self::into_contiguous_buf).
+    #[cfg_attr(feature = "alloc", doc = "")]
+    #[cfg_attr(feature = "alloc", doc = "See also [`into_contiguous_buf()`](Self::into_contiguous_buf).")]
     #[inline(always)]
     #[allow(deprecated)]
     pub fn buf_mut(&mut self) -> &mut Container { &mut self.buf }
 
-    /// Get the pixel storage by consuming the image. Be careful about stride — see [`into_contiguous_buf()`](Self::into_contiguous_buf) for a safe version.
+    /// Get the pixel storage by consuming the image. Be careful about stride.
+    #[cfg_attr(feature = "alloc", doc = "See [`into_contiguous_buf()`](Self::into_contiguous_buf) for a safe version.")]
     #[inline(always)]
     #[allow(deprecated)]
     pub fn into_buf(self) -> Container { self.buf }
@@ -300,6 +474,15 @@ fn sub_image(left: usize, top: usize, width: usize, height: usize, stride: usize
     (start, end, stride)
 }
 
+/// `TooLarge` error if the dimensions can't be stored in `Img` (`new_stride` checks them only in debug builds)
+#[inline]
+fn check_dimensions(width: usize, height: usize) -> Result<(), ImgError> {
+    if width >= u32::MAX as usize || height >= u32::MAX as usize {
+        return Err(ImgError::TooLarge { width, height });
+    }
+    Ok(())
+}
+
 impl<'slice, T> ImgRef<'slice, T> {
     /// Make a reference for a part of the image, without copying any pixels.
     ///
@@ -345,6 +528,7 @@ impl<'slice, T> ImgRef<'slice, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T: Clone> ImgRef<'a, T> {
     /// Returns a reference to the buffer, width, height. Guarantees that the buffer is contiguous,
     /// i.e. it's `width*height` elements long, and `[x + y*width]` addresses each pixel.
@@ -366,6 +550,31 @@ impl<'a, T: Clone> ImgRef<'a, T> {
         }
         (Cow::Owned(buf), width, height)
     }
+
+    /// Same as [`to_contiguous_buf()`](Self::to_contiguous_buf), but returns an error instead of aborting when memory can't be allocated
+    #[allow(deprecated)]
+    pub fn try_to_contiguous_buf(&self) -> Result<(Cow<'a, [T]>, usize, usize), ImgError> {
+        let width = self.width();
+        let height = self.height();
+        if width == self.stride() {
+            return Ok((Cow::Borrowed(self.buf), width, height));
+        }
+        let mut buf = builder::try_with_capacity(width, height)?;
+        for row in self.rows() {
+            buf.extend_from_slice(row);
+        }
+        Ok((Cow::Owned(buf), width, height))
+    }
+
+    /// Copy the pixels into a new image without padding. Returns an error instead of aborting when memory can't be allocated.
+    pub fn try_to_owned(&self) -> Result<ImgVec<T>, ImgError> {
+        let (width, height) = (self.width(), self.height());
+        let mut buf = builder::try_with_capacity(width, height)?;
+        for row in self.rows() {
+            buf.extend_from_slice(row);
+        }
+        Ok(Img::new_stride(buf, width, height, width.max(1)))
+    }
 }
 
 impl<'slice, T> ImgRefMut<'slice, T> {
@@ -466,6 +675,12 @@ impl<T: Copy> ImgRefMut<'_, T> {
 }
 
 impl<T> ImgRefMut<'_, T> {
+    /// Iterate `width*height` pixels in the `Img`, by reference, ignoring padding area
+    #[inline]
+    pub fn pixels_ref(&self) -> PixelsRefIter<'_, T> {
+        PixelsRefIter::new(self.as_ref())
+    }
+
     /// If you want to iterate in parallel, parallelize `rows()` instead.
     /// # Panics
     ///
@@ -476,6 +691,7 @@ impl<T> ImgRefMut<'_, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Copy> ImgVec<T> {
     /// If you want to iterate in parallel, parallelize `rows()` instead.
     /// # Panics
@@ -488,7 +704,14 @@ impl<T: Copy> ImgVec<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T> ImgVec<T> {
+    /// Iterate `width*height` pixels in the `Img`, by reference, ignoring padding area
+    #[inline]
+    pub fn pixels_ref(&self) -> PixelsRefIter<'_, T> {
+        PixelsRefIter::new(self.as_ref())
+    }
+
     /// If you want to iterate in parallel, parallelize `rows()` instead.
     /// # Panics
     ///
@@ -541,6 +764,7 @@ impl<Container> IntoIterator for Img<Container> where Container: IntoIterator {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T> ImgVec<T> {
     /// Create a mutable view into a region within the image. See `sub_image()` for read-only views.
     ///
@@ -715,6 +939,7 @@ impl<'buf, T> ImgRef<'buf, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Copy> Img<Vec<T>> {
     /// Returns the buffer, width, height. Guarantees that the buffer is contiguous,
     /// i.e. it's `width*height` elements long, and `[x + y*width]` addresses each pixel.
@@ -785,6 +1010,7 @@ impl<OldContainer> Img<OldContainer> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Clone> From<Img<Cow<'_, [T]>>> for Img<Vec<T>> {
     #[allow(deprecated)]
     fn from(img: Img<Cow<'_, [T]>>) -> Self {
@@ -797,6 +1023,7 @@ impl<T: Clone> From<Img<Cow<'_, [T]>>> for Img<Vec<T>> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Clone> From<ImgVec<T>> for Img<Cow<'static, [T]>> {
     #[allow(deprecated)]
     fn from(img: ImgVec<T>) -> Self {
@@ -809,6 +1036,7 @@ impl<T: Clone> From<ImgVec<T>> for Img<Cow<'static, [T]>> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T: Clone> From<ImgRef<'a, T>> for Img<Cow<'a, [T]>> {
     #[allow(deprecated)]
     fn from(img: ImgRef<'a, T>) -> Self {
@@ -821,6 +1049,7 @@ impl<'a, T: Clone> From<ImgRef<'a, T>> for Img<Cow<'a, [T]>> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Clone> Img<Cow<'_, [T]>> {
     /// Convert underlying buffer to owned (e.g. slice to vec)
     ///
@@ -842,8 +1071,17 @@ impl<T: Clone> Img<Cow<'_, [T]>> {
             },
         }
     }
+
+    /// Same as [`into_owned()`](Self::into_owned), but returns an error instead of aborting when memory can't be allocated
+    pub fn try_into_owned(self) -> Result<ImgVec<T>, ImgError> {
+        match self.buf() {
+            Cow::Borrowed(_) => self.as_ref().try_to_owned(),
+            Cow::Owned(_) => Ok(self.into_owned()),
+        }
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<T> Img<T> where T: ToOwned {
     /// Convert underlying buffer to owned (e.g. slice to vec)
     ///
@@ -859,7 +1097,7 @@ impl<T> Img<T> where T: ToOwned {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
     use alloc::vec;
@@ -1019,3 +1257,16 @@ mod tests {
         assert_eq!(pixels, buf);
     }
 }
+
+#[test]
+fn framebuffer_without_alloc() {
+    let mut framebuffer = [0u16; 8 * 4];
+    let mut img = Img::new_stride(&mut framebuffer[..], 6, 4, 8);
+    img[(5usize, 3usize)] = 1;
+    img.sub_image_mut(1, 1, 2, 2).pixels_mut().for_each(|px| *px = 2);
+    img.flip_vertical_in_place();
+    assert_eq!(&[0, 0, 0, 0, 0, 1], &img[0]);
+    assert_eq!(9, img.pixels().map(u32::from).sum::<u32>());
+    assert_eq!(Some(rect::Rect::new(1, 0, 5, 3)), trim::content_bounds(img.as_ref(), |&px| px != 0));
+    assert_eq!(3, img.rows().filter(|row| row.iter().any(|&px| px != 0)).count());
+}
@@ -0,0 +1,119 @@
+//! Float functions that are missing from `core` in `no_std`
+
+use core::f64::consts::{LN_10, LN_2};
+
+/// Natural logarithm. Returns NaN for negative numbers and -∞ for 0.
+pub(crate) fn ln(x: f64) -> f64 {
+    if x.is_nan() || x < 0. {
+        return f64::NAN;
+    }
+    if x == 0. {
+        return f64::NEG_INFINITY;
+    }
+    if x.is_infinite() {
+        return x;
+    }
+    // split into mantissa in [1, 2) and exponent
+    let (mut mantissa, mut exponent) = (x, 0i32);
+    if mantissa < f64::MIN_POSITIVE {
+        mantissa *= (1u64 << 54) as f64;
+        exponent -= 54;
+    }
+    let bits = mantissa.to_bits();
+    exponent += ((bits >> 52) & 0x7FF) as i32 - 1023;
+    let mut mantissa = f64::from_bits((bits & !(0x7FF << 52)) | (1023 << 52));
+    // keep it close to 1 for faster convergence
+    if mantissa > core::f64::consts::SQRT_2 {
+        mantissa /= 2.;
+        exponent += 1;
+    }
+    // ln(m) = 2 atanh((m-1)/(m+1))
+    let s = (mantissa - 1.) / (mantissa + 1.);
+    let s2 = s * s;
+    let mut term = s;
+    let mut sum = 0.;
+    let mut n = 1.;
+    while abs(term) > f64::EPSILON * 1e-3 {
+        sum += term / n;
+        term *= s2;
+        n += 2.;
+    }
+    2. * sum + f64::from(exponent) * LN_2
+}
+
+pub(crate) fn log10(x: f64) -> f64 {
+    ln(x) / LN_10
+}
+
+/// e^x
+#[cfg(feature = "alloc")] // only SSIM needs it
+pub(crate) fn exp(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x > 709.8 {
+        return f64::INFINITY;
+    }
+    if x < -745.2 {
+        return 0.;
+    }
+    // x = k ln2 + r, where |r| <= ln2/2
+    let k = (x / LN_2 + if x < 0. { -0.5 } else { 0.5 }) as i32;
+    // ln2 split into high and low parts, so that k * LN_2_HI is exact
+    const LN_2_HI: f64 = 6.931_471_803_691_238e-1;
+    const LN_2_LO: f64 = 1.908_214_929_270_587_7e-10;
+    let r = (x - f64::from(k) * LN_2_HI) - f64::from(k) * LN_2_LO;
+    let mut term = 1.;
+    let mut sum = 1.;
+    let mut n = 1.;
+    while abs(term) > f64::EPSILON * 1e-3 {
+        term *= r / n;
+        sum += term;
+        n += 1.;
+    }
+    // multiply by 2^k in two steps, so that neither factor overflows
+    let half = k / 2;
+    sum * pow2(half) * pow2(k - half)
+}
+
+/// `base` to the power of `exponent`, for non-negative `base`
+#[cfg(feature = "alloc")]
+pub(crate) fn powf(base: f64, exponent: f64) -> f64 {
+    if exponent == 0. {
+        return 1.;
+    }
+    if base == 0. {
+        return if exponent > 0. { 0. } else { f64::INFINITY };
+    }
+    exp(exponent * ln(base))
+}
+
+#[cfg(feature = "alloc")]
+fn pow2(k: i32) -> f64 {
+    f64::from_bits(((k + 1023) as u64) << 52)
+}
+
+#[inline(always)]
+pub(crate) fn abs(x: f64) -> f64 {
+    if x < 0. { -x } else { x }
+}
+
+#[test]
+fn accuracy() {
+    for &x in &[1e-310, 1e-300, 1e-10, 0.001, 0.5, 0.99, 1., 1.5, 2., 3., 10., 255., 65025., 1e10, 1e300] {
+        let rel = |a: f64, b: f64| ((a - b) / b).abs();
+        assert!(rel(ln(x), x.ln()) < 1e-14 || (ln(x) - x.ln()).abs() < 1e-15, "ln {x}");
+        assert!(rel(log10(x), x.log10()) < 1e-14 || (log10(x) - x.log10()).abs() < 1e-15, "log10 {x}");
+    }
+    #[cfg(feature = "alloc")]
+    {
+        for &x in &[-700., -20., -1., -0.3, 0., 0.3, 1., 2.5, 20., 700.] {
+            assert!(((exp(x) - x.exp()) / x.exp()).abs() < 1e-14, "exp {x}");
+        }
+        for &(b, e) in &[(0.5, 0.0448), (0.9, 0.2856), (2., 10.), (10., -2.), (0., 0.5)] {
+            assert!((powf(b, e) - f64::powf(b, e)).abs() < 1e-14, "{b}^{e}");
+        }
+    }
+    assert!(ln(-1.).is_nan());
+    assert_eq!(f64::NEG_INFINITY, ln(0.));
+}
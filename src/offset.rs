@@ -0,0 +1,181 @@
+use crate::{Img, ImgExt, ImgVec, Rect};
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+/// Owned image that doesn't have to start at the beginning of its `Vec`.
+///
+/// Cropping it with [`into_sub_image()`](ImgVecOffset::into_sub_image) doesn't copy any pixels.
+pub type ImgVecOffset<Pixel> = Img<OffsetVec<Pixel>>;
+
+/// `Vec` with some elements at the beginning skipped. It's the container used by [`ImgVecOffset`].
+#[derive(Debug, Clone, Default)]
+pub struct OffsetVec<T> {
+    vec: Vec<T>,
+    offset: usize,
+}
+
+impl<T> OffsetVec<T> {
+    /// Skip `offset` elements at the beginning of the `vec`
+    ///
+    /// # Panics
+    ///
+    /// If `offset` is larger than the length of the `vec`
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn new(vec: Vec<T>, offset: usize) -> Self {
+        assert!(offset <= vec.len());
+        Self { vec, offset }
+    }
+
+    /// Number of skipped elements
+    #[inline]
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The whole `Vec` and the number of skipped elements
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> (Vec<T>, usize) {
+        (self.vec, self.offset)
+    }
+}
+
+impl<T> Deref for OffsetVec<T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        &self.vec[self.offset..]
+    }
+}
+
+impl<T> DerefMut for OffsetVec<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.vec[self.offset..]
+    }
+}
+
+impl<T> AsRef<[T]> for OffsetVec<T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> AsMut<[T]> for OffsetVec<T> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T> From<ImgVec<T>> for ImgVecOffset<T> {
+    #[inline]
+    fn from(img: ImgVec<T>) -> Self {
+        img.map_buf(|vec| OffsetVec { vec, offset: 0 })
+    }
+}
+
+impl<T> From<ImgVecOffset<T>> for ImgVec<T> {
+    /// Removes the skipped elements from the `Vec`. It keeps the stride and doesn't reallocate.
+    #[inline]
+    fn from(img: ImgVecOffset<T>) -> Self {
+        img.map_buf(|OffsetVec { mut vec, offset }| {
+            vec.drain(..offset);
+            vec
+        })
+    }
+}
+
+impl<T> ImgVec<T> {
+    /// Crop the image without copying any pixels. Pixels after the cropped area are dropped.
+    ///
+    /// Convert the result back to `ImgVec` with `.into()`, or get a buffer with [`ImgVecOffset::into_contiguous_buf()`].
+    ///
+    /// # Panics
+    ///
+    /// If the rect is out of bounds
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn into_sub_image(self, rect: Rect) -> ImgVecOffset<T> {
+        ImgVecOffset::from(self).into_sub_image(rect)
+    }
+}
+
+impl<T> ImgVecOffset<T> {
+    /// Crop the image without copying any pixels. Pixels after the cropped area are dropped.
+    ///
+    /// The `rect` is relative to this image.
+    ///
+    /// # Panics
+    ///
+    /// If the rect is out of bounds
+    #[must_use]
+    #[track_caller]
+    pub fn into_sub_image(self, rect: Rect) -> Self {
+        assert!(rect.bottom() <= self.height());
+        assert!(rect.right() <= self.width());
+        let stride = self.stride();
+        let OffsetVec { mut vec, offset } = self.into_buf();
+        let (start, end, stride) = crate::sub_image(rect.left, rect.top, rect.width, rect.height, stride, vec.len() - offset);
+        vec.truncate(offset + end);
+        Img::new_stride(OffsetVec { vec, offset: offset + start }, rect.width, rect.height, stride)
+    }
+}
+
+impl<T: Copy> ImgVecOffset<T> {
+    /// Returns the buffer, width, height. Guarantees that the buffer is contiguous,
+    /// i.e. it's `width*height` elements long, and `[x + y*width]` addresses each pixel.
+    ///
+    /// Pixels are moved to the beginning of the `Vec` only if needed.
+    #[must_use]
+    pub fn into_contiguous_buf(self) -> (Vec<T>, usize, usize) {
+        let (width, height, stride) = (self.width(), self.height(), self.stride());
+        let OffsetVec { mut vec, offset } = self.into_buf();
+        if offset > 0 || stride != width {
+            for y in 0..height {
+                let start = offset + y * stride;
+                vec.copy_within(start..start + width, y * width);
+            }
+        }
+        vec.truncate(width * height);
+        (vec, width, height)
+    }
+
+    /// Copy the pixels into a new contiguous image
+    #[must_use]
+    pub fn to_img_vec(&self) -> ImgVec<T> {
+        let (buf, width, height) = self.as_ref().to_contiguous_buf();
+        Img::new_stride(buf.into_owned(), width, height, width.max(1))
+    }
+}
+
+#[test]
+fn offset_crop() {
+    use crate::ImgExtMut;
+    use alloc::vec;
+
+    let img = ImgVec::from_fn(5, 4, |x, y| (x + 10 * y) as u8);
+    let ptr = img.buf().as_ptr();
+    let mut sub = img.into_sub_image(Rect::new(1, 1, 3, 2));
+    assert_eq!(6, sub.buf().offset());
+    assert_eq!(sub.as_ref(), Img::new(vec![11, 12, 13, 21, 22, 23], 3, 2));
+    assert_eq!(12, sub[(1usize, 0usize)]);
+    sub[1][2] = 99;
+    sub.rows_mut().for_each(|row| row[0] += 100);
+
+    let sub = sub.into_sub_image(Rect::new(1, 1, 2, 1));
+    assert_eq!(&[22, 99], &sub[0]);
+    assert_eq!(ImgVec::from(sub.clone()), Img::new(vec![22, 99], 2, 1));
+    let (buf, w, h) = sub.into_contiguous_buf();
+    assert_eq!((&[22, 99][..], 2, 1), (&buf[..], w, h));
+    assert_eq!(ptr, buf.as_ptr());
+
+    let whole: ImgVecOffset<u8> = Img::new(vec![1, 2, 3, 4], 2, 2).into();
+    assert_eq!((vec![1, 2, 3, 4], 2, 2), whole.into_contiguous_buf());
+}
@@ -1,9 +1,8 @@
 use super::Img;
-use alloc::vec::Vec;
 use core::ops;
 
-#[cfg(test)]
-use alloc::vec;
+#[cfg(all(test, feature = "alloc"))]
+use alloc::{borrow::Cow, rc::Rc, vec, vec::Vec};
 
 macro_rules! impl_imgref_index {
     ($container:ty, $index:ty) => {
@@ -51,12 +50,9 @@ impl_imgref_index! {&'a mut [Pixel], usize}
 impl_imgref_index! {&'a mut [Pixel], u32}
 impl_imgref_index_mut! {&'a mut [Pixel], usize}
 impl_imgref_index_mut! {&'a mut [Pixel], u32}
-impl_imgref_index! {Vec<Pixel>, usize}
-impl_imgref_index! {Vec<Pixel>, u32}
-impl_imgref_index_mut! {Vec<Pixel>, usize}
-impl_imgref_index_mut! {Vec<Pixel>, u32}
 
 #[test]
+#[cfg(feature = "alloc")]
 fn index() {
     let mut img = Img::new_stride(vec![1,2,3,4,5,6,7,8], 2, 2, 3);
     assert_eq!(1, img[(0u32,0u32)]);
@@ -108,8 +104,58 @@ macro_rules! impl_imgref_row_index_mut {
 impl_imgref_row_index! {&'a [Pixel]}
 impl_imgref_row_index! {&'a mut [Pixel]}
 impl_imgref_row_index_mut! {&'a mut [Pixel]}
-impl_imgref_row_index! {Vec<Pixel>}
-impl_imgref_row_index_mut! {Vec<Pixel>}
+#[cfg(feature = "alloc")]
+// Vec and other containers that need a heap
+mod containers {
+    use super::*;
+    use crate::OffsetVec;
+    use alloc::borrow::Cow;
+    use alloc::boxed::Box;
+    use alloc::rc::Rc;
+    #[cfg(target_has_atomic = "ptr")]
+    use alloc::sync::Arc;
+    #[cfg(target_has_atomic = "ptr")]
+    use crate::ArcSlice;
+    use alloc::vec::Vec;
+
+    impl_imgref_index! {Vec<Pixel>, usize}
+    impl_imgref_index! {Vec<Pixel>, u32}
+    impl_imgref_index_mut! {Vec<Pixel>, usize}
+    impl_imgref_index_mut! {Vec<Pixel>, u32}
+    impl_imgref_index! {OffsetVec<Pixel>, usize}
+    impl_imgref_index! {OffsetVec<Pixel>, u32}
+    impl_imgref_index_mut! {OffsetVec<Pixel>, usize}
+    impl_imgref_index_mut! {OffsetVec<Pixel>, u32}
+    impl_imgref_index! {Box<[Pixel]>, usize}
+    impl_imgref_index! {Box<[Pixel]>, u32}
+    impl_imgref_index_mut! {Box<[Pixel]>, usize}
+    impl_imgref_index_mut! {Box<[Pixel]>, u32}
+    impl_imgref_index! {Cow<'a, [Pixel]>, usize}
+    impl_imgref_index! {Cow<'a, [Pixel]>, u32}
+    impl_imgref_index! {Rc<[Pixel]>, usize}
+    impl_imgref_index! {Rc<[Pixel]>, u32}
+    #[cfg(target_has_atomic = "ptr")]
+    impl_imgref_index! {Arc<[Pixel]>, usize}
+    #[cfg(target_has_atomic = "ptr")]
+    impl_imgref_index! {Arc<[Pixel]>, u32}
+    #[cfg(target_has_atomic = "ptr")]
+    impl_imgref_index! {ArcSlice<Pixel>, usize}
+    #[cfg(target_has_atomic = "ptr")]
+    impl_imgref_index! {ArcSlice<Pixel>, u32}
+
+    impl_imgref_row_index! {Vec<Pixel>}
+    impl_imgref_row_index_mut! {Vec<Pixel>}
+    impl_imgref_row_index! {OffsetVec<Pixel>}
+    impl_imgref_row_index_mut! {OffsetVec<Pixel>}
+    impl_imgref_row_index! {Box<[Pixel]>}
+    impl_imgref_row_index_mut! {Box<[Pixel]>}
+    impl_imgref_row_index! {Cow<'a, [Pixel]>}
+    impl_imgref_row_index! {Rc<[Pixel]>}
+    #[cfg(target_has_atomic = "ptr")]
+    impl_imgref_row_index! {Arc<[Pixel]>}
+    #[cfg(target_has_atomic = "ptr")]
+    impl_imgref_row_index! {ArcSlice<Pixel>}
+}
 
 #[cold]
 fn index_fail(row: usize) -> ! {
@@ -117,6 +163,7 @@ fn index_fail(row: usize) -> ! {
 }
 
 #[test]
+#[cfg(feature = "alloc")]
 fn index_by_row() {
     let mut img = Img::new_stride(vec![1,2,3,4,5,6,7,8], 2, 2, 3);
     assert_eq!(&[1,2], &img[0]);
@@ -126,3 +173,27 @@ fn index_by_row() {
     assert_eq!(&[1,2], &img.as_mut()[0]);
     assert_eq!(&[4,5], &img.as_mut()[1]);
 }
+
+#[test]
+#[cfg(feature = "alloc")]
+fn index_other_containers() {
+    use crate::{ImgExt, ImgExtMut};
+
+    let mut boxed = Img::new_stride(vec![1,2,3,4,5,6,7,8].into_boxed_slice(), 2, 2, 3);
+    boxed[(1usize,1usize)] = 50;
+    boxed[1][0] = 40;
+    assert_eq!(&[40,50], &boxed[1]);
+    boxed.rows_mut().for_each(|row| row[0] += 1);
+    boxed.pixels_mut().for_each(|px| *px += 1);
+    assert_eq!(vec![3,3,42,51], boxed.pixels().collect::<Vec<_>>());
+    assert_eq!(&[42], &boxed.sub_image_mut(0,1,1,1)[0]);
+
+    let cow = Img::new_stride(Cow::Borrowed(&[1,2,3,4,5][..]), 2, 2, 3);
+    assert_eq!(5, cow[(1u32,1u32)]);
+    assert_eq!(vec![&[1,2][..], &[4,5]], cow.rows().collect::<Vec<_>>());
+    assert_eq!(&4, cow.pixels_ref().nth(2).unwrap());
+
+    let rc = Img::new(Rc::<[u8]>::from(&[1,2,3,4][..]), 2, 2);
+    assert_eq!(&[3,4], &rc[1]);
+    assert_eq!(Img::new(vec![2,4], 1, 2), rc.sub_image(1,0,1,2));
+}
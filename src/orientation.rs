@@ -0,0 +1,147 @@
+#[cfg(feature = "alloc")]
+use crate::{Img, ImgRef, ImgVec};
+
+#[cfg(all(test, feature = "alloc"))]
+use alloc::vec;
+
+/// Orientation of the stored pixels, as defined by the EXIF/TIFF `Orientation` tag.
+///
+/// The variant describes the transformation that needs to be applied to the stored image
+/// to display it upright.
+#[cfg_attr(feature = "alloc", doc = "See [`apply_orientation()`].")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Orientation {
+    /// The image is already upright
+    Normal = 1,
+    /// Mirrored left to right
+    FlipHorizontal = 2,
+    /// Upside down
+    Rotate180 = 3,
+    /// Mirrored top to bottom
+    FlipVertical = 4,
+    /// Mirrored along the top-left to bottom-right diagonal
+    Transpose = 5,
+    /// Needs to be rotated 90° clockwise
+    Rotate90 = 6,
+    /// Mirrored along the top-right to bottom-left diagonal
+    Transverse = 7,
+    /// Needs to be rotated 90° counter-clockwise
+    Rotate270 = 8,
+}
+
+impl Default for Orientation {
+    #[inline]
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+impl Orientation {
+    /// Interpret value of the EXIF/TIFF `Orientation` tag. Returns `None` if it's not in `1..=8`.
+    #[must_use]
+    pub const fn from_exif(value: u16) -> Option<Self> {
+        Some(match value {
+            1 => Self::Normal,
+            2 => Self::FlipHorizontal,
+            3 => Self::Rotate180,
+            4 => Self::FlipVertical,
+            5 => Self::Transpose,
+            6 => Self::Rotate90,
+            7 => Self::Transverse,
+            8 => Self::Rotate270,
+            _ => return None,
+        })
+    }
+
+    /// Value of the EXIF/TIFF `Orientation` tag for this orientation
+    #[inline]
+    #[must_use]
+    pub const fn to_exif(self) -> u16 {
+        self as u16
+    }
+
+    /// The orientation that undoes this one, e.g. for writing an upright image back in its stored orientation.
+    #[must_use]
+    pub const fn inverse(self) -> Self {
+        match self {
+            Self::Rotate90 => Self::Rotate270,
+            Self::Rotate270 => Self::Rotate90,
+            other => other,
+        }
+    }
+
+    /// Whether width and height of the image are swapped after applying the orientation
+    #[inline]
+    #[must_use]
+    pub const fn swaps_dimensions(self) -> bool {
+        matches!(self, Self::Transpose | Self::Rotate90 | Self::Transverse | Self::Rotate270)
+    }
+}
+
+/// Transform the image from its stored orientation to the upright one.
+///
+/// Flips and 180° rotation are performed in place. Other orientations swap width and height,
+/// so they make a new image.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn apply_orientation<T: Copy>(mut img: ImgVec<T>, orientation: Orientation) -> ImgVec<T> {
+    match orientation {
+        Orientation::Normal => {},
+        Orientation::FlipHorizontal => img.flip_horizontal_in_place(),
+        Orientation::Rotate180 => img.rotate_180_in_place(),
+        Orientation::FlipVertical => img.flip_vertical_in_place(),
+        _ => return apply_orientation_ref(img.as_ref(), orientation),
+    }
+    img
+}
+
+/// Make an upright copy of an image that is stored in the given orientation.
+///
+/// See [`apply_orientation()`] for a version that reuses the buffer when possible.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn apply_orientation_ref<T: Copy>(img: ImgRef<'_, T>, orientation: Orientation) -> ImgVec<T> {
+    let mut out = match orientation {
+        Orientation::Transpose | Orientation::Transverse => img.transpose(),
+        Orientation::Rotate90 => return img.rotate_90(),
+        Orientation::Rotate270 => return img.rotate_270(),
+        _ => Img::new_stride(img.pixels().collect(), img.width(), img.height(), img.width().max(1)),
+    };
+    match orientation {
+        Orientation::FlipHorizontal => out.flip_horizontal_in_place(),
+        Orientation::Rotate180 | Orientation::Transverse => out.rotate_180_in_place(),
+        Orientation::FlipVertical => out.flip_vertical_in_place(),
+        _ => {},
+    }
+    out
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn orientations() {
+    // stored images as they'd look for the letter "F" in each orientation
+    let upright = Img::new(vec![1, 1,
+                                1, 0,
+                                1, 1,
+                                1, 0], 2, 4);
+    let stored = [
+        (Orientation::Normal, upright.clone()),
+        (Orientation::FlipHorizontal, Img::new(vec![1, 1, 0, 1, 1, 1, 0, 1], 2, 4)),
+        (Orientation::Rotate180, Img::new(vec![0, 1, 1, 1, 0, 1, 1, 1], 2, 4)),
+        (Orientation::FlipVertical, Img::new(vec![1, 0, 1, 1, 1, 0, 1, 1], 2, 4)),
+        (Orientation::Transpose, Img::new(vec![1, 1, 1, 1, 1, 0, 1, 0], 4, 2)),
+        (Orientation::Rotate90, Img::new(vec![1, 0, 1, 0, 1, 1, 1, 1], 4, 2)),
+        (Orientation::Transverse, Img::new(vec![0, 1, 0, 1, 1, 1, 1, 1], 4, 2)),
+        (Orientation::Rotate270, Img::new(vec![1, 1, 1, 1, 0, 1, 0, 1], 4, 2)),
+    ];
+    for (o, img) in stored {
+        assert_eq!(Some(o), Orientation::from_exif(o.to_exif()));
+        assert_eq!(o.swaps_dimensions(), img.width() != upright.width());
+        assert_eq!(upright, apply_orientation_ref(img.as_ref(), o), "{o:?}");
+        assert_eq!(upright, apply_orientation(img.clone(), o), "{o:?}");
+        assert_eq!(img, apply_orientation(upright.clone(), o.inverse()), "{o:?}");
+    }
+    assert_eq!(None, Orientation::from_exif(0));
+    assert_eq!(None, Orientation::from_exif(9));
+}
@@ -0,0 +1,106 @@
+//! Synthetic images for tests and benchmarks.
+//!
+//! The `*_padded` functions and [`padded()`] make images with a stride larger than the width,
+//! and fill the padding with a sentinel value. Use [`padding_is()`] to check that the padding hasn't been written to,
+//! and pick a sentinel that stands out (e.g. `f32::INFINITY` or `0xFF`) to catch code that reads it.
+
+use crate::{Img, ImgRef, ImgVec};
+use alloc::vec;
+
+/// Grayscale ramp from 0 at the left edge to 255 at the right edge
+#[must_use]
+pub fn gradient_horizontal(width: usize, height: usize) -> ImgVec<u8> {
+    let max = width.saturating_sub(1).max(1);
+    ImgVec::from_fn(width, height, |x, _| (x * 255 / max) as u8)
+}
+
+/// Grayscale ramp from 0 at the top edge to 255 at the bottom edge
+#[must_use]
+pub fn gradient_vertical(width: usize, height: usize) -> ImgVec<u8> {
+    let max = height.saturating_sub(1).max(1);
+    ImgVec::from_fn(width, height, |_, y| (y * 255 / max) as u8)
+}
+
+/// Alternating `cell`×`cell` squares of `a` and `b`, starting with `a` in the top-left corner
+///
+/// # Panics
+///
+/// If `cell` is 0
+#[must_use]
+#[track_caller]
+pub fn checkerboard<T: Copy>(width: usize, height: usize, cell: usize, a: T, b: T) -> ImgVec<T> {
+    assert!(cell > 0);
+    ImgVec::from_fn(width, height, |x, y| if (x / cell + y / cell) % 2 == 0 { a } else { b })
+}
+
+/// Pseudo-random pixel values. The same `seed` always gives the same image.
+#[must_use]
+pub fn noise(width: usize, height: usize, seed: u64) -> ImgVec<u8> {
+    let mut state = seed;
+    ImgVec::from_fn(width, height, |_, _| {
+        // splitmix64
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        (z ^ (z >> 31)) as u8
+    })
+}
+
+/// Copy of the image with `pad` pixels of padding after every row (including the last one), set to `sentinel`
+#[must_use]
+pub fn padded<T: Copy>(img: ImgRef<'_, T>, pad: usize, sentinel: T) -> ImgVec<T> {
+    let width = img.width();
+    let stride = (width + pad).max(1);
+    let mut buf = vec![sentinel; stride * img.height()];
+    for (dst, src) in buf.chunks_mut(stride).zip(img.rows()) {
+        dst[..width].copy_from_slice(src);
+    }
+    Img::new_stride(buf, width, img.height(), stride)
+}
+
+/// Same as [`gradient_horizontal()`], but with `pad` pixels of padding after every row, set to `sentinel`
+#[must_use]
+pub fn gradient_horizontal_padded(width: usize, height: usize, pad: usize, sentinel: u8) -> ImgVec<u8> {
+    padded(gradient_horizontal(width, height).as_ref(), pad, sentinel)
+}
+
+/// Same as [`checkerboard()`], but with `pad` pixels of padding after every row, set to `sentinel`
+#[must_use]
+#[track_caller]
+pub fn checkerboard_padded<T: Copy>(width: usize, height: usize, cell: usize, a: T, b: T, pad: usize, sentinel: T) -> ImgVec<T> {
+    padded(checkerboard(width, height, cell, a, b).as_ref(), pad, sentinel)
+}
+
+/// Same as [`noise()`], but with `pad` pixels of padding after every row, set to `sentinel`
+#[must_use]
+pub fn noise_padded(width: usize, height: usize, seed: u64, pad: usize, sentinel: u8) -> ImgVec<u8> {
+    padded(noise(width, height, seed).as_ref(), pad, sentinel)
+}
+
+/// Check that all of the padding between `width` and `stride` in every row equals `sentinel`
+#[must_use]
+pub fn padding_is<T: PartialEq>(img: ImgRef<'_, T>, sentinel: &T) -> bool {
+    let width = img.width();
+    img.buf().chunks(img.stride()).take(img.height()).all(|row| row.get(width..).unwrap_or_default().iter().all(|px| px == sentinel))
+}
+
+#[test]
+fn patterns() {
+    let g = gradient_horizontal(5, 2);
+    assert_eq!(&[0, 63, 127, 191, 255], &g[1]);
+    assert_eq!(&[0, 255], &gradient_vertical(2, 2).buf()[1..3]);
+    assert_eq!(&[1, 1, 2, 2, 1], &checkerboard(5, 3, 2, 1, 2)[1]);
+    assert_eq!(&[2, 2, 1, 1, 2], &checkerboard(5, 3, 2, 1, 2)[2]);
+    assert_eq!(noise(7, 3, 1), noise(7, 3, 1));
+    assert_ne!(noise(7, 3, 1), noise(7, 3, 2));
+
+    let mut img = noise_padded(7, 3, 1, 2, 0xAA);
+    assert_eq!((7, 9), (img.width(), img.stride()));
+    assert_eq!(27, img.buf().len());
+    assert_eq!(img, noise(7, 3, 1));
+    assert!(padding_is(img.as_ref(), &0xAA));
+    img.buf_mut()[8] = 0;
+    assert!(!padding_is(img.as_ref(), &0xAA));
+    assert!(padding_is(checkerboard_padded(3, 3, 1, 0u8, 1, 1, 9).as_ref(), &9));
+}
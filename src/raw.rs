@@ -0,0 +1,89 @@
+use crate::{Img, ImgError, ImgRef, ImgRefMut};
+use core::{mem, slice};
+
+/// Stride in pixels and length of the slice, or an error if they can't describe a valid image
+fn raw_layout<T>(ptr: *const T, width: usize, height: usize, stride_bytes: usize) -> Result<(usize, usize), ImgError> {
+    if ptr.is_null() {
+        return Err(ImgError::NullPointer);
+    }
+    if ptr as usize % mem::align_of::<T>() != 0 {
+        return Err(ImgError::Unaligned { align: mem::align_of::<T>() });
+    }
+    crate::check_dimensions(width, height)?;
+    let pixel_size = mem::size_of::<T>();
+    // zero-sized pixels can have any stride
+    let stride = stride_bytes.checked_div(pixel_size).unwrap_or(width);
+    if stride < width || stride_bytes.checked_rem(pixel_size).map_or(false, |rem| rem != 0) {
+        return Err(ImgError::InvalidStride { stride_bytes, pixel_size });
+    }
+    // rows of zero-width images don't have any pixels, so they can be anywhere
+    let stride = stride.max(1);
+    // same as valid_min_len(), the last row doesn't need padding
+    let len = if height == 0 || width == 0 { 0 } else {
+        stride.checked_mul(height - 1)
+            .and_then(|len| len.checked_add(width))
+            .filter(|&len| len.checked_mul(pixel_size).map_or(false, |bytes| bytes <= isize::MAX as usize))
+            .ok_or(ImgError::TooLarge { width, height })?
+    };
+    Ok((stride, len))
+}
+
+impl<'slice, T> ImgRef<'slice, T> {
+    /// Make an image from a pointer to its first pixel, e.g. a frame received from a C library.
+    ///
+    /// `stride_bytes` is the distance between starts of rows in bytes. It has to be a multiple of `size_of::<T>()`.
+    /// Only `stride * (height - 1) + width` pixels are borrowed, so the last row doesn't need padding.
+    ///
+    /// Returns an error if the pointer is null or not aligned, the stride is not a whole number of pixels or is smaller than the width,
+    /// or a dimension is `u32::MAX` or larger.
+    /// The stride of zero-width images can be 0.
+    ///
+    /// # Safety
+    ///
+    /// The memory described by the arguments must be valid for reads and initialized, and must not be mutated for the lifetime `'slice`.
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *const T, width: usize, height: usize, stride_bytes: usize) -> Result<Self, ImgError> {
+        let (stride, len) = raw_layout(ptr, width, height, stride_bytes)?;
+        Ok(Img::new_stride(slice::from_raw_parts(ptr, len), width, height, stride))
+    }
+}
+
+impl<'slice, T> ImgRefMut<'slice, T> {
+    /// Make a mutable image from a pointer to its first pixel. See [`ImgRef::from_raw_parts()`].
+    ///
+    /// # Safety
+    ///
+    /// The memory described by the arguments must be valid for reads and writes and initialized,
+    /// and must not be accessed through any other pointer for the lifetime `'slice`.
+    #[inline]
+    pub unsafe fn from_raw_parts_mut(ptr: *mut T, width: usize, height: usize, stride_bytes: usize) -> Result<Self, ImgError> {
+        let (stride, len) = raw_layout(ptr, width, height, stride_bytes)?;
+        Ok(Img::new_stride(slice::from_raw_parts_mut(ptr, len), width, height, stride))
+    }
+}
+
+#[test]
+fn raw_parts() {
+    let mut pixels = [1u16, 2, 3, 0, 4, 5, 6, 0, 7, 8, 9];
+    let img = unsafe { ImgRef::from_raw_parts(pixels.as_ptr(), 3, 3, 8) }.unwrap();
+    assert_eq!(img, Img::new_stride(&pixels[..], 3, 3, 4));
+    assert_eq!(11, img.buf().len());
+
+    let mut img = unsafe { ImgRefMut::from_raw_parts_mut(pixels.as_mut_ptr().add(1), 2, 2, 8) }.unwrap();
+    img[(1usize, 1usize)] = 60;
+    assert_eq!(60, pixels[6]);
+
+    let ptr = pixels.as_ptr();
+    assert_eq!(Some(ImgError::InvalidStride { stride_bytes: 7, pixel_size: 2 }), unsafe { ImgRef::from_raw_parts(ptr, 3, 2, 7) }.err());
+    assert_eq!(Some(ImgError::InvalidStride { stride_bytes: 4, pixel_size: 2 }), unsafe { ImgRef::from_raw_parts(ptr, 3, 2, 4) }.err());
+    assert_eq!(Some(ImgError::InvalidStride { stride_bytes: 0, pixel_size: 2 }), unsafe { ImgRef::from_raw_parts(ptr, 1, 2, 0) }.err());
+    assert_eq!(Some(ImgError::NullPointer), unsafe { ImgRef::<u16>::from_raw_parts(core::ptr::null(), 1, 1, 2) }.err());
+    assert_eq!(Some(ImgError::Unaligned { align: 2 }), unsafe { ImgRef::from_raw_parts(ptr.cast::<u8>().add(1).cast::<u16>(), 1, 1, 2) }.err());
+    assert_eq!(Some(ImgError::TooLarge { width: 1, height: usize::MAX }), unsafe { ImgRef::from_raw_parts(ptr, 1, usize::MAX, 4) }.err());
+    assert_eq!(Some(ImgError::TooLarge { width: 1, height: u32::MAX as usize }), unsafe { ImgRef::from_raw_parts(ptr, 1, u32::MAX as usize, 4) }.err());
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(Some(ImgError::TooLarge { width: 1 << 32, height: 0 }), unsafe { ImgRef::from_raw_parts(ptr, 1 << 32, 0, 1 << 33) }.err());
+
+    let empty = unsafe { ImgRef::from_raw_parts(ptr, 0, 5, 0) }.unwrap();
+    assert_eq!((0, 1), (empty.buf().len(), empty.stride()));
+}
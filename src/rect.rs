@@ -0,0 +1,44 @@
+/// A rectangular area of an image, in pixels.
+///
+/// Same as the arguments of [`sub_image()`](crate::ImgRef::sub_image).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Rect {
+    /// Distance from the left edge of the image
+    pub left: usize,
+    /// Distance from the top edge of the image
+    pub top: usize,
+    /// Width of the area
+    pub width: usize,
+    /// Height of the area
+    pub height: usize,
+}
+
+impl Rect {
+    /// Rectangle at the given position and size
+    #[inline]
+    #[must_use]
+    pub const fn new(left: usize, top: usize, width: usize, height: usize) -> Self {
+        Self { left, top, width, height }
+    }
+
+    /// First column to the right of the area (exclusive end)
+    #[inline]
+    #[must_use]
+    pub const fn right(&self) -> usize {
+        self.left + self.width
+    }
+
+    /// First row below the area (exclusive end)
+    #[inline]
+    #[must_use]
+    pub const fn bottom(&self) -> usize {
+        self.top + self.height
+    }
+
+    /// Whether the area has no pixels
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
@@ -0,0 +1,142 @@
+use crate::ImgRef;
+use core::fmt;
+
+/// Asserts that two images have the same size and pixels, ignoring padding.
+///
+/// Works with `ImgVec`, `ImgRef` and `ImgRefMut`. Unlike `assert_eq!`, on failure it doesn't print the whole buffers,
+/// but the first differing pixel, number of differing pixels, and a rough map of where the differences are.
+///
+/// ```rust
+/// use imgref::*;
+/// let a = Img::new(vec![1, 2, 3, 4], 2, 2);
+/// let b = Img::new_stride(vec![1, 2, 0, 3, 4], 2, 2, 3);
+/// assert_img_eq!(a, b.as_ref());
+/// ```
+#[macro_export]
+macro_rules! assert_img_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__assert_img_eq($crate::ImgExt::as_ref(&$left), $crate::ImgExt::as_ref(&$right), |a, b| a == b)
+    };
+}
+
+/// Asserts that two images have the same size, and their pixels differ by at most `tolerance`.
+///
+/// Pixels must be convertible to `f64` (e.g. `f32` or `u8`). See [`assert_img_eq!`].
+///
+/// ```rust
+/// use imgref::*;
+/// let a = Img::new(vec![0.5f32, 1.0], 2, 1);
+/// let b = Img::new(vec![0.501f32, 0.999], 2, 1);
+/// assert_img_approx_eq!(a, b, 0.01);
+/// ```
+#[macro_export]
+macro_rules! assert_img_approx_eq {
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        $crate::__assert_img_eq($crate::ImgExt::as_ref(&$left), $crate::ImgExt::as_ref(&$right), |a, b| $crate::__approx_eq(*a, *b, $tolerance))
+    };
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn __approx_eq<T: Copy + Into<f64>>(a: T, b: T, tolerance: impl Into<f64>) -> bool {
+    let tolerance = tolerance.into();
+    let diff = a.into() - b.into();
+    diff <= tolerance && -diff <= tolerance
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_img_eq<T: fmt::Debug>(left: ImgRef<'_, T>, right: ImgRef<'_, T>, same: impl Fn(&T, &T) -> bool) {
+    if (left.width(), left.height()) != (right.width(), right.height()) {
+        panic!("images differ in size: left is {}×{}, right is {}×{}", left.width(), left.height(), right.width(), right.height());
+    }
+    let mut first = None;
+    let mut count = 0;
+    for (y, (l, r)) in left.rows().zip(right.rows()).enumerate() {
+        for (x, (a, b)) in l.iter().zip(r).enumerate() {
+            if !same(a, b) {
+                count += 1;
+                first.get_or_insert((x, y, a, b));
+            }
+        }
+    }
+    if let Some((x, y, a, b)) = first {
+        panic!("images differ at {count} of {} pixels; first at ({x}, {y}): left = {a:?}, right = {b:?}\n{}",
+            left.width() * left.height(), DiffMap { left, right, same });
+    }
+}
+
+/// Draws a low-res map of differences, one character per block of pixels
+struct DiffMap<'a, T, F> {
+    left: ImgRef<'a, T>,
+    right: ImgRef<'a, T>,
+    same: F,
+}
+
+impl<T, F: Fn(&T, &T) -> bool> fmt::Display for DiffMap<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const MAX_COLUMNS: usize = 64;
+        const MAX_ROWS: usize = 32;
+        let width = self.left.width();
+        let height = self.left.height();
+        let block_width = (width + MAX_COLUMNS - 1) / MAX_COLUMNS;
+        let block_height = (height + MAX_ROWS - 1) / MAX_ROWS;
+        writeln!(f, "differences ({block_width}×{block_height} pixels per character):")?;
+        for top in (0..height).step_by(block_height) {
+            let bottom = (top + block_height).min(height);
+            for left in (0..width).step_by(block_width) {
+                let right = (left + block_width).min(width);
+                let differs = self.left.rows().zip(self.right.rows()).skip(top).take(bottom - top).any(|(l, r)| {
+                    l[left..right].iter().zip(&r[left..right]).any(|(a, b)| !(self.same)(a, b))
+                });
+                f.write_str(if differs { "#" } else { "." })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{Img, ImgVec};
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec;
+
+    fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+        let err = std::panic::catch_unwind(f).unwrap_err();
+        err.downcast_ref::<String>().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn diff_report() {
+        let a = ImgVec::from_fn(100, 40, |x, y| (x + y) as u8);
+        let mut b = Img::new_stride(vec![0; 101 * 40], 100, 40, 101);
+        b.pixels_mut().zip(a.pixels()).for_each(|(b, a)| *b = a);
+        assert_img_eq!(a, b);
+        assert_img_eq!(a.as_ref(), b.as_mut());
+
+        b[(50usize, 20usize)] = 0;
+        b[(99usize, 39usize)] = 0;
+        let msg = panic_message(|| assert_img_eq!(a, b));
+        assert!(msg.starts_with("images differ at 2 of 4000 pixels; first at (50, 20): left = 70, right = 0\n"), "{msg}");
+        let map = msg.lines().skip(2).collect::<vec::Vec<_>>();
+        assert_eq!(20, map.len());
+        assert_eq!(format!("{}#{}", ".".repeat(25), ".".repeat(24)), map[10]);
+        assert_eq!(format!("{}#", ".".repeat(49)), map[19]);
+
+        let msg = panic_message(|| assert_img_eq!(a, a.sub_image(0, 0, 10, 10)));
+        assert_eq!("images differ in size: left is 100×40, right is 10×10", msg);
+    }
+
+    #[test]
+    fn approx() {
+        let a = Img::new(vec![0.5f32, 1.0, 0.], 3, 1);
+        let b = Img::new(vec![0.49f32, 1.01, 0.], 3, 1);
+        assert_img_approx_eq!(a, b, 0.02);
+        assert_img_approx_eq!(a.as_ref(), b.as_ref(), 0.02f32);
+        let msg = panic_message(|| assert_img_approx_eq!(a, b, 0.001));
+        assert!(msg.starts_with("images differ at 2 of 3 pixels; first at (0, 0)"), "{msg}");
+    }
+}
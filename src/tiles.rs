@@ -0,0 +1,233 @@
+use crate::{Img, ImgExt, ImgRef, ImgRefMut, Rect};
+use core::iter::FusedIterator;
+
+#[cfg(all(test, feature = "alloc"))]
+use alloc::vec;
+#[cfg(all(test, feature = "alloc"))]
+use alloc::vec::Vec;
+
+impl<'slice, T> ImgRef<'slice, T> {
+    /// Split the image into tiles that overlap their neighbors.
+    ///
+    /// The image is divided into a grid of `tile`×`tile` areas (smaller at the right and bottom edges),
+    /// which are the [`core`](Tile::core) of each tile. Every tile additionally includes up to `overlap` pixels
+    /// of its neighbors on each side, so that filters with wide kernels can see past the core's edges.
+    ///
+    /// Use [`assemble_from_tiles()`] to stitch processed tiles back together.
+    ///
+    /// # Panics
+    ///
+    /// If `tile` is 0
+    #[inline]
+    #[track_caller]
+    pub fn tiles_with_overlap(&self, tile: usize, overlap: usize) -> TilesIter<'slice, T> {
+        assert!(tile > 0);
+        let columns = (self.width() + tile - 1) / tile;
+        TilesIter {
+            img: *self,
+            tile,
+            overlap,
+            x: 0,
+            y: 0,
+            columns,
+            rows: if columns > 0 { (self.height() + tile - 1) / tile } else { 0 },
+        }
+    }
+}
+
+/// A fragment of an image, with a margin overlapping its neighbors. Created by [`ImgRef::tiles_with_overlap()`].
+#[derive(Debug, Copy, Clone)]
+pub struct Tile<Container> {
+    /// Position and size of the whole tile (including the overlap) in the source image
+    pub rect: Rect,
+    /// The area that this tile is responsible for, in coordinates of the source image. Cores of tiles don't overlap.
+    pub core: Rect,
+    /// Pixels of the tile. It has size of `rect`.
+    pub img: Img<Container>,
+}
+
+impl<Container> Tile<Container> {
+    /// Position of the core relative to the tile's top-left corner
+    #[inline]
+    #[must_use]
+    pub fn core_in_tile(&self) -> Rect {
+        Rect::new(self.core.left - self.rect.left, self.core.top - self.rect.top, self.core.width, self.core.height)
+    }
+
+    /// Replace the pixels of the tile, keeping its position. Useful for giving results of processing back to [`assemble_from_tiles()`].
+    #[inline]
+    pub fn map_img<NewContainer, F>(self, callback: F) -> Tile<NewContainer> where F: FnOnce(Img<Container>) -> Img<NewContainer> {
+        Tile {
+            rect: self.rect,
+            core: self.core,
+            img: callback(self.img),
+        }
+    }
+}
+
+/// Copy the core of every tile to its position in `dst`.
+///
+/// # Panics
+///
+/// If a tile's image doesn't have the size of its `rect`, or the tile is outside of `dst`.
+#[track_caller]
+pub fn assemble_from_tiles<T, Container, I>(mut dst: ImgRefMut<'_, T>, tiles: I)
+    where T: Copy, Container: AsRef<[T]>, I: IntoIterator<Item = Tile<Container>> {
+    for tile in tiles {
+        let src = check_tile(&tile);
+        let core = tile.core_in_tile();
+        let src = src.sub_image(core.left, core.top, core.width, core.height);
+        let mut dst = dst.sub_image_mut(tile.core.left, tile.core.top, tile.core.width, tile.core.height);
+        for (dst_row, src_row) in dst.rows_mut().zip(src.rows()) {
+            dst_row.copy_from_slice(src_row);
+        }
+    }
+}
+
+/// Like [`assemble_from_tiles()`], but smoothly blends tiles across the seams to hide discontinuities.
+///
+/// Each tile also writes `feather` pixels outside of its core, and pixels within `feather` distance of a seam
+/// are mixed using `blend(old_pixel, tile_pixel, weight)`, where `weight` is between 0 and 1
+/// (1 means only the tile's pixel should be used).
+///
+/// Tiles must be given in the order they've been created (left to right, top to bottom).
+///
+/// # Panics
+///
+/// If `feather` is larger than the overlap of the tiles, a tile's image doesn't have the size of its `rect`, or the tile is outside of `dst`.
+#[track_caller]
+pub fn assemble_from_tiles_feathered<T, Container, I, F>(mut dst: ImgRefMut<'_, T>, tiles: I, feather: usize, mut blend: F)
+    where T: Copy, Container: AsRef<[T]>, I: IntoIterator<Item = Tile<Container>>, F: FnMut(T, T, f32) -> T {
+    let ramp = |pos: usize, seam: usize| -> f32 {
+        if seam == 0 || pos >= seam + feather {
+            return 1.;
+        }
+        ((pos + feather - seam) as f32 + 0.5) / (2 * feather) as f32
+    };
+    for tile in tiles {
+        let src = check_tile(&tile);
+        let core = tile.core;
+        let written = Rect::new(core.left.saturating_sub(feather), core.top.saturating_sub(feather), 0, 0);
+        let written = Rect {
+            width: (core.right() + feather).min(dst.width()) - written.left,
+            height: (core.bottom() + feather).min(dst.height()) - written.top,
+            ..written
+        };
+        assert!(written.left >= tile.rect.left && written.top >= tile.rect.top &&
+            written.right() <= tile.rect.right() && written.bottom() <= tile.rect.bottom(), "feather is larger than the tile overlap");
+
+        for y in written.top..written.bottom() {
+            let weight_y = ramp(y, core.top);
+            let src_row = &src[y - tile.rect.top];
+            let dst_row = &mut dst[y];
+            for x in written.left..written.right() {
+                let px = src_row[x - tile.rect.left];
+                let weight = weight_y * ramp(x, core.left);
+                dst_row[x] = if weight >= 1. { px } else { blend(dst_row[x], px, weight) };
+            }
+        }
+    }
+}
+
+#[track_caller]
+fn check_tile<T, Container: AsRef<[T]>>(tile: &Tile<Container>) -> ImgRef<'_, T> {
+    let img = tile.img.as_ref();
+    assert_eq!((tile.rect.width, tile.rect.height), (img.width(), img.height()), "the tile image has a wrong size");
+    img
+}
+
+/// Iterator over overlapping tiles of an image. Created by [`ImgRef::tiles_with_overlap()`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct TilesIter<'slice, T> {
+    img: ImgRef<'slice, T>,
+    tile: usize,
+    overlap: usize,
+    x: usize,
+    y: usize,
+    columns: usize,
+    rows: usize,
+}
+
+impl<'slice, T> Iterator for TilesIter<'slice, T> {
+    type Item = Tile<&'slice [T]>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= self.rows {
+            return None;
+        }
+        let (x, y) = (self.x, self.y);
+        self.x += 1;
+        if self.x >= self.columns {
+            self.x = 0;
+            self.y += 1;
+        }
+        let width = self.img.width();
+        let height = self.img.height();
+        let core_left = x * self.tile;
+        let core_top = y * self.tile;
+        let core = Rect::new(core_left, core_top, self.tile.min(width - core_left), self.tile.min(height - core_top));
+        let left = core.left.saturating_sub(self.overlap);
+        let top = core.top.saturating_sub(self.overlap);
+        let rect = Rect::new(left, top,
+            (core.right() + self.overlap).min(width) - left,
+            (core.bottom() + self.overlap).min(height) - top);
+        Some(Tile {
+            rect,
+            core,
+            img: self.img.sub_image(rect.left, rect.top, rect.width, rect.height),
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for TilesIter<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        (self.rows.saturating_sub(self.y) * self.columns).saturating_sub(self.x)
+    }
+}
+
+impl<T> FusedIterator for TilesIter<'_, T> {}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn tiles() {
+    let src = Img::new((0..70u16).collect::<Vec<_>>(), 10, 7);
+    let tiles: Vec<_> = src.as_ref().tiles_with_overlap(4, 2).collect();
+    assert_eq!(6, tiles.len());
+    assert_eq!(Rect::new(0, 0, 6, 6), tiles[0].rect);
+    assert_eq!(Rect::new(2, 0, 8, 6), tiles[1].rect);
+    assert_eq!(Rect::new(4, 0, 4, 4), tiles[1].core);
+    assert_eq!(Rect::new(2, 0, 4, 4), tiles[1].core_in_tile());
+    assert_eq!(Rect::new(6, 2, 4, 5), tiles[5].rect);
+    assert_eq!(Rect::new(8, 4, 2, 3), tiles[5].core);
+    assert_eq!(tiles[5].img, src.sub_image(6, 2, 4, 5));
+
+    let mut out = Img::new(vec![0u16; 70], 10, 7);
+    let processed = tiles.iter().map(|t| t.map_img(|img| Img::new(img.pixels().map(|px| px + 1).collect::<Vec<_>>(), img.width(), img.height())));
+    assemble_from_tiles(out.as_mut(), processed.clone());
+    assert!(src.pixels().zip(out.pixels()).all(|(a, b)| a + 1 == b));
+
+    let mut out = Img::new(vec![0u16; 70], 10, 7);
+    assemble_from_tiles_feathered(out.as_mut(), processed, 2, |old, new, weight| {
+        assert!(weight > 0. && weight < 1.);
+        assert_eq!(old, new);
+        new
+    });
+    assert!(src.pixels().zip(out.pixels()).all(|(a, b)| a + 1 == b));
+
+    let flat = Img::new(vec![0u8; 64], 8, 8);
+    let mut out = Img::new(vec![0u8; 64], 8, 8);
+    let tiles = flat.as_ref().tiles_with_overlap(4, 1).enumerate()
+        .map(|(i, t)| t.map_img(|img| img.new_buf(vec![i as u8 * 50; img.buf().len()])));
+    assemble_from_tiles_feathered(out.as_mut(), tiles, 1, |old, new, w| (f32::from(old) * (1. - w) + f32::from(new) * w) as u8);
+    assert_eq!(&out[0], &[0, 0, 0, 12, 37, 50, 50, 50]);
+    assert_eq!(&out[7], &[100, 100, 100, 112, 137, 150, 150, 150]);
+}
@@ -1,4 +1,6 @@
-use crate::{ImgRef, ImgRefMut, ImgVec};
+use crate::{ImgRef, ImgRefMut};
+#[cfg(feature = "alloc")]
+use crate::ImgVec;
 use core::hash::{Hash, Hasher};
 
 impl<T: Hash> Hash for ImgRef<'_, T> {
@@ -21,6 +23,7 @@ impl<T: Hash> Hash for ImgRefMut<'_, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Hash> Hash for ImgVec<T> {
     #[inline(always)]
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -47,6 +50,7 @@ impl<'b, T, U> PartialEq<ImgRefMut<'b, U>> for ImgRefMut<'_, T> where T: Partial
 }
 
 
+#[cfg(feature = "alloc")]
 impl<T, U> PartialEq<ImgVec<U>> for ImgVec<T> where T: PartialEq<U> {
     #[allow(deprecated)]
     #[inline(always)]
@@ -55,6 +59,7 @@ impl<T, U> PartialEq<ImgVec<U>> for ImgVec<T> where T: PartialEq<U> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T, U> PartialEq<ImgRef<'a, U>> for ImgVec<T> where T: PartialEq<U> {
     #[allow(deprecated)]
     #[inline(always)]
@@ -63,6 +68,7 @@ impl<'a, T, U> PartialEq<ImgRef<'a, U>> for ImgVec<T> where T: PartialEq<U> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, U> PartialEq<ImgVec<U>> for ImgRef<'_, T> where T: PartialEq<U> {
     #[allow(deprecated)]
     #[inline(always)]
@@ -93,10 +99,12 @@ impl<T: Eq> Eq for ImgRefMut<'_, T> {
 impl<T: Eq> Eq for ImgRef<'_, T> {
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Eq> Eq for ImgVec<T> {
 }
 
 #[test]
+#[cfg(feature = "alloc")]
 fn test_eq_hash() {
     use alloc::vec;
 
@@ -133,12 +141,12 @@ fn test_eq_hash() {
     assert!(!map.insert(img2));
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 use std::collections::HashSet;
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 use std::fmt::Debug;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 fn equiv<A>(a: &A, b: &A) where A: Eq + PartialEq + Hash + Debug {
     assert_eq!(a, b);
     let mut map = HashSet::new();
@@ -0,0 +1,186 @@
+use crate::ImgRefMut;
+#[cfg(feature = "alloc")]
+use crate::{Img, ImgRef, ImgVec};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(all(test, feature = "alloc"))]
+use alloc::vec;
+
+impl<T> ImgRefMut<'_, T> {
+    /// Reverse order of rows, so that the top row becomes the bottom one.
+    ///
+    /// Rows are swapped in place, without allocating. Padding is not touched.
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn flip_vertical_in_place(&mut self) {
+        let width = self.width();
+        let height = self.height();
+        let stride = self.stride();
+        let buf = self.valid_buf_mut();
+        for top in 0..height / 2 {
+            let bottom = height - 1 - top;
+            let (upper, lower) = buf.split_at_mut(bottom * stride);
+            upper[top * stride..top * stride + width].swap_with_slice(&mut lower[..width]);
+        }
+    }
+
+    /// Reverse order of pixels in every row, so that the left edge becomes the right one.
+    pub fn flip_horizontal_in_place(&mut self) {
+        for row in self.rows_mut() {
+            row.reverse();
+        }
+    }
+
+    /// Rotate the image by 180°. Same as flipping it both vertically and horizontally.
+    ///
+    /// Dimensions stay the same, so this works in place.
+    pub fn rotate_180_in_place(&mut self) {
+        self.flip_vertical_in_place();
+        self.flip_horizontal_in_place();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ImgVec<T> {
+    /// Reverse order of rows, so that the top row becomes the bottom one.
+    ///
+    /// Rows are swapped in place, without allocating. Padding is not touched.
+    #[inline]
+    pub fn flip_vertical_in_place(&mut self) {
+        self.as_mut().flip_vertical_in_place();
+    }
+
+    /// Reverse order of pixels in every row, so that the left edge becomes the right one.
+    #[inline]
+    pub fn flip_horizontal_in_place(&mut self) {
+        self.as_mut().flip_horizontal_in_place();
+    }
+
+    /// Rotate the image by 180°. Same as flipping it both vertically and horizontally.
+    #[inline]
+    pub fn rotate_180_in_place(&mut self) {
+        self.as_mut().rotate_180_in_place();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Copy> ImgRef<'_, T> {
+    /// Make a copy of the image rotated 90° clockwise.
+    ///
+    /// The new image has width and height swapped, and no padding.
+    #[must_use]
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn rotate_90(&self) -> ImgVec<T> {
+        let width = self.width();
+        let height = self.height();
+        let stride = self.stride();
+        let buf = self.valid_buf();
+        let mut out = Vec::with_capacity(width * height);
+        for x in 0..width {
+            out.extend((0..height).rev().map(|y| buf[y * stride + x]));
+        }
+        Img::new_stride(out, height, width, height.max(1))
+    }
+
+    /// Make a copy of the image rotated 270° clockwise (90° counter-clockwise).
+    ///
+    /// The new image has width and height swapped, and no padding.
+    #[must_use]
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn rotate_270(&self) -> ImgVec<T> {
+        let width = self.width();
+        let height = self.height();
+        let stride = self.stride();
+        let buf = self.valid_buf();
+        let mut out = Vec::with_capacity(width * height);
+        for x in (0..width).rev() {
+            out.extend((0..height).map(|y| buf[y * stride + x]));
+        }
+        Img::new_stride(out, height, width, height.max(1))
+    }
+
+    /// Make a copy of the image mirrored along its top-left to bottom-right diagonal,
+    /// so that `out[(x,y)] == self[(y,x)]`.
+    ///
+    /// The new image has width and height swapped, and no padding.
+    #[must_use]
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn transpose(&self) -> ImgVec<T> {
+        let width = self.width();
+        let height = self.height();
+        let stride = self.stride();
+        let buf = self.valid_buf();
+        let mut out = Vec::with_capacity(width * height);
+        for x in 0..width {
+            out.extend((0..height).map(|y| buf[y * stride + x]));
+        }
+        Img::new_stride(out, height, width, height.max(1))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Copy> ImgVec<T> {
+    /// Make a copy of the image rotated 90° clockwise. See [`ImgRef::rotate_90`].
+    #[inline]
+    #[must_use]
+    pub fn rotate_90(&self) -> Self {
+        self.as_ref().rotate_90()
+    }
+
+    /// Make a copy of the image rotated 90° counter-clockwise. See [`ImgRef::rotate_270`].
+    #[inline]
+    #[must_use]
+    pub fn rotate_270(&self) -> Self {
+        self.as_ref().rotate_270()
+    }
+
+    /// Make a copy of the image mirrored along its diagonal. See [`ImgRef::transpose`].
+    #[inline]
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.as_ref().transpose()
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn flips() {
+    let mut img = Img::new_stride(vec![1, 2, 3, 0,
+                                       4, 5, 6, 0,
+                                       7, 8, 9], 3, 3, 4);
+    img.flip_vertical_in_place();
+    assert_eq!(img, Img::new(vec![7, 8, 9, 4, 5, 6, 1, 2, 3], 3, 3));
+    img.flip_horizontal_in_place();
+    assert_eq!(img, Img::new(vec![9, 8, 7, 6, 5, 4, 3, 2, 1], 3, 3));
+    img.rotate_180_in_place();
+    assert_eq!(img, Img::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 9], 3, 3));
+    assert_eq!(0, img.buf()[3]);
+
+    let mut img = Img::new(vec![1, 2, 3, 4], 1, 4);
+    img.as_mut().sub_image_mut(0, 1, 1, 3).flip_vertical_in_place();
+    assert_eq!(&[1, 4, 3, 2], &img.buf()[..]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn rotations() {
+    let img = Img::new_stride(vec![1, 2, 3, 0,
+                                   4, 5, 6], 3, 2, 4);
+    let cw = img.rotate_90();
+    assert_eq!(cw, Img::new(vec![4, 1,
+                                 5, 2,
+                                 6, 3], 2, 3));
+    let ccw = img.rotate_270();
+    assert_eq!(ccw, Img::new(vec![3, 6,
+                                  2, 5,
+                                  1, 4], 2, 3));
+    assert_eq!(img, ccw.rotate_90());
+    assert_eq!(img, cw.rotate_270());
+    let t = img.transpose();
+    assert_eq!(t, Img::new(vec![1, 4,
+                                2, 5,
+                                3, 6], 2, 3));
+    assert_eq!(img, t.transpose());
+    assert_eq!(0, Img::new_stride(vec![0u8; 0], 0, 5, 1).rotate_90().height());
+    assert_eq!(0, Img::new(vec![0u8; 0], 5, 0).rotate_270().width());
+}
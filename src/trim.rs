@@ -0,0 +1,57 @@
+use crate::{ImgRef, Rect};
+
+/// Finds the smallest area that contains all pixels for which `predicate` returns `true`.
+///
+/// Returns `None` if there are no such pixels. For example, `content_bounds(img, |px| px.a > 0)` finds the non-transparent part of an image.
+#[must_use]
+pub fn content_bounds<T, F>(img: ImgRef<'_, T>, mut predicate: F) -> Option<Rect> where F: FnMut(&T) -> bool {
+    let top = img.rows().position(|row| row.iter().any(&mut predicate))?;
+    let bottom = img.rows().rposition(|row| row.iter().any(&mut predicate)).unwrap_or(top);
+    let width = img.width();
+    let (mut left, mut right) = (width, 0);
+    for row in img.rows().skip(top).take(bottom + 1 - top) {
+        // only the pixels outside of the bounds found so far need to be checked
+        if let Some(x) = row[..left].iter().position(&mut predicate) {
+            left = x;
+        }
+        if let Some(x) = row[right.max(left)..].iter().rposition(&mut predicate) {
+            right = right.max(left) + x + 1;
+        }
+        if left == 0 && right == width {
+            break;
+        }
+    }
+    Some(Rect::new(left, top, right - left, bottom + 1 - top))
+}
+
+/// Crops the image to the [`content_bounds()`], without copying.
+///
+/// If no pixels match the `predicate`, the result is an empty (0×0) image.
+#[must_use]
+pub fn trim<'slice, T, F>(img: ImgRef<'slice, T>, predicate: F) -> ImgRef<'slice, T> where F: FnMut(&T) -> bool {
+    let rect = content_bounds(img, predicate).unwrap_or_default();
+    img.sub_image(rect.left, rect.top, rect.width, rect.height)
+}
+
+#[test]
+fn trimming() {
+    use crate::Img;
+
+    let img = Img::new_stride(&[
+        0, 0, 0, 0, 0, 9,
+        0, 0, 1, 0, 0, 9,
+        0, 2, 0, 0, 0, 9,
+        0, 0, 0, 3, 0, 9,
+        0, 0, 0, 0, 0,
+    ][..], 5, 5, 6);
+    assert_eq!(Some(Rect::new(1, 1, 3, 3)), content_bounds(img, |&px| px != 0));
+    assert_eq!(Some(Rect::new(2, 1, 1, 1)), content_bounds(img, |&px| px == 1));
+    assert_eq!(None, content_bounds(img, |&px| px == 9));
+    assert_eq!(Some(Rect::new(0, 0, 5, 5)), content_bounds(img, |&px| px == 0));
+
+    let trimmed = trim(img, |&px| px != 0);
+    assert_eq!(trimmed, Img::new(&[0, 1, 0, 2, 0, 0, 0, 0, 3][..], 3, 3));
+    assert_eq!(trim(trimmed, |&px| px > 1), Img::new(&[2, 0, 0, 0, 0, 3][..], 3, 2));
+    let empty = trim(img, |_| false);
+    assert_eq!((0, 0), (empty.width(), empty.height()));
+}
@@ -0,0 +1,169 @@
+use crate::canvas::buf_len;
+use crate::{Img, ImgRefMut, ImgVec};
+use alloc::vec::Vec;
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+impl<T> ImgVec<T> {
+    /// Allocate an image without initializing its pixels. It has no padding.
+    ///
+    /// Write all pixels with [`write_row()`](ImgRefMut::write_row) or [`write()`](ImgRefMut::write), and then call [`assume_init()`](Img::assume_init).
+    #[must_use]
+    pub fn new_uninit(width: usize, height: usize) -> ImgVec<MaybeUninit<T>> {
+        Self::new_uninit_stride(width, height, width.max(1))
+    }
+
+    /// Allocate an image with rows `stride` pixels apart, without initializing its pixels.
+    ///
+    /// The buffer is `stride * height` long, so the padding has to be initialized too, e.g. with [`init_padding()`](ImgRefMut::init_padding).
+    ///
+    /// # Panics
+    ///
+    /// If `stride` is 0 or smaller than `width`, or `stride * height` overflows `usize`
+    #[must_use]
+    #[track_caller]
+    pub fn new_uninit_stride(width: usize, height: usize, stride: usize) -> ImgVec<MaybeUninit<T>> {
+        let len = buf_len(stride, height);
+        let mut buf = Vec::with_capacity(len);
+        // SAFETY: MaybeUninit doesn't need to be initialized
+        unsafe { buf.set_len(len) };
+        Img::new_stride(buf, width, height, stride)
+    }
+}
+
+impl<T> ImgVec<MaybeUninit<T>> {
+    /// Initialize the pixel at `(x, y)`. See [`ImgRefMut::write()`].
+    #[inline]
+    #[track_caller]
+    pub fn write(&mut self, x: usize, y: usize, value: T) {
+        self.as_mut().write(x, y, value);
+    }
+
+    /// Initialize a row of pixels. See [`ImgRefMut::write_row()`].
+    #[inline]
+    #[track_caller]
+    pub fn write_row(&mut self, y: usize, row: &[T]) where T: Copy {
+        self.as_mut().write_row(y, row);
+    }
+
+    /// Set all pixels in the padding to `value`. See [`ImgRefMut::init_padding()`].
+    #[inline]
+    pub fn init_padding(&mut self, value: T) where T: Clone {
+        self.as_mut().init_padding(value);
+    }
+
+    /// Convert to an image of initialized pixels, without copying.
+    ///
+    /// # Safety
+    ///
+    /// Every element of the buffer must have been initialized, including the padding between rows and after the last row.
+    #[must_use]
+    pub unsafe fn assume_init(self) -> ImgVec<T> {
+        let (width, height, stride) = (self.width(), self.height(), self.stride());
+        let mut buf = ManuallyDrop::new(self.into_buf());
+        // SAFETY: MaybeUninit<T> has the same layout as T, and the caller guarantees all elements are initialized
+        let buf = Vec::from_raw_parts(buf.as_mut_ptr().cast::<T>(), buf.len(), buf.capacity());
+        Img::new_stride(buf, width, height, stride)
+    }
+}
+
+impl<'slice, T> ImgRefMut<'slice, MaybeUninit<T>> {
+    /// A view of the unused capacity of the `vec`, big enough for a `width`×`height` image without padding.
+    ///
+    /// It reserves the space if needed. After writing all the pixels, call `vec.set_len(vec.len() + width * height)`.
+    ///
+    /// # Panics
+    ///
+    /// If `width * height` overflows `usize`
+    #[must_use]
+    #[track_caller]
+    pub fn from_spare_capacity(vec: &'slice mut Vec<T>, width: usize, height: usize) -> Self {
+        let len = buf_len(width, height);
+        vec.reserve(len);
+        Img::new_stride(&mut vec.spare_capacity_mut()[..len], width, height, width.max(1))
+    }
+
+    /// Initialize the pixel at `(x, y)`
+    ///
+    /// # Panics
+    ///
+    /// If the coordinates are out of bounds
+    #[inline]
+    #[track_caller]
+    pub fn write(&mut self, x: usize, y: usize, value: T) {
+        assert!(x < self.width() && y < self.height());
+        let stride = self.stride();
+        self.buf_mut()[y * stride + x] = MaybeUninit::new(value);
+    }
+
+    /// Initialize a row of pixels
+    ///
+    /// # Panics
+    ///
+    /// If `row` isn't exactly `width` pixels long, or `y` is out of bounds
+    #[track_caller]
+    pub fn write_row(&mut self, y: usize, row: &[T]) where T: Copy {
+        assert_eq!(self.width(), row.len());
+        assert!(y < self.height());
+        let start = y * self.stride();
+        for (dst, &src) in self.buf_mut()[start..start + row.len()].iter_mut().zip(row) {
+            *dst = MaybeUninit::new(src);
+        }
+    }
+
+    /// Set all elements of the buffer outside of `width`×`height` to `value`.
+    ///
+    /// This doesn't touch the pixels, but makes it safe to [`assume_init()`](Img::assume_init) once they're written.
+    pub fn init_padding(&mut self, value: T) where T: Clone {
+        let width = self.width();
+        let stride = self.stride();
+        let height = self.height();
+        let buf = self.buf_mut();
+        let pixels_end = if width == 0 { 0 } else { (height * stride).min(buf.len()) };
+        for row in buf[..pixels_end].chunks_mut(stride) {
+            for px in row.iter_mut().skip(width) {
+                *px = MaybeUninit::new(value.clone());
+            }
+        }
+        for px in &mut buf[pixels_end..] {
+            *px = MaybeUninit::new(value.clone());
+        }
+    }
+}
+
+#[test]
+fn uninit() {
+    use alloc::vec;
+
+    let mut img = ImgVec::new_uninit(3, 2);
+    img.write_row(0, &[1u8, 2, 3]);
+    img.write(0, 1, 4);
+    img.as_mut().sub_image_mut(1, 1, 2, 1).write_row(0, &[5, 6]);
+    let img = unsafe { img.assume_init() };
+    assert_eq!(img, Img::new(vec![1, 2, 3, 4, 5, 6], 3, 2));
+
+    let mut img = ImgVec::<u16>::new_uninit_stride(2, 2, 3);
+    img.write_row(0, &[1, 2]);
+    img.write_row(1, &[3, 4]);
+    img.init_padding(0);
+    let img = unsafe { img.assume_init() };
+    assert_eq!(&[1, 2, 0, 3, 4, 0], &img.buf()[..]);
+
+    let mut vec = vec![9u8];
+    let mut view = ImgRefMut::from_spare_capacity(&mut vec, 2, 1);
+    view.write_row(0, &[7, 8]);
+    unsafe { vec.set_len(3) };
+    assert_eq!(vec![9, 7, 8], vec);
+}
+
+#[test]
+#[should_panic(expected = "image is too large")]
+fn uninit_overflow() {
+    let _ = ImgVec::<u8>::new_uninit_stride(2, usize::MAX / 2, 4);
+}
+
+#[test]
+#[should_panic(expected = "image is too large")]
+fn spare_capacity_overflow() {
+    let mut vec = Vec::<u8>::new();
+    let _ = ImgRefMut::from_spare_capacity(&mut vec, 3, usize::MAX / 2);
+}